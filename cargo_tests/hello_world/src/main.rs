@@ -34,6 +34,9 @@ fn main() {
     sb.append_char('\r');
     let mstr = sb.to_mstring();
     mycorrhiza::system::console::Console::writeln_string(mstr);
+    const GREETING: &[u8] = "Zażółć gęślą jaźń".as_bytes();
+    let mstr = mycorrhiza::system::MString::from_utf8(GREETING);
+    mycorrhiza::system::console::Console::writeln_string(mstr);
     use mycorrhiza::std::vec::Vec;
     
 }
@@ -1,70 +1,207 @@
+/// Defines `staticN`, `instanceN`, `virtN` and `ctorN` helpers of [`RustcCLRInteropManagedClass`], which call managed methods with N arguments.
+macro_rules! managed_class_helpers {
+    ($($static:ident, $instance:ident, $virt:ident, $ctor:ident => $call:ident, $call_with_self:ident, $call_virt_with_self:ident, $ctor_intrinsic:ident($($arg:ident: $arg_ty:ident),+);)*) => {
+        $(
+            #[inline(always)]
+            pub fn $static<const METHOD: &'static str, $($arg_ty,)+ Ret>($($arg: $arg_ty),+) -> Ret {
+                $call::<ASSEMBLY, CLASS_PATH, false, Generics, METHOD, true, Ret, $($arg_ty),+>($($arg),+)
+            }
+            #[inline(always)]
+            pub fn $instance<const METHOD: &'static str, $($arg_ty,)+ Ret>(self, $($arg: $arg_ty),+) -> Ret {
+                $call_with_self::<ASSEMBLY, CLASS_PATH, false, Generics, METHOD, false, Ret, Self, $($arg_ty),+>(self, $($arg),+)
+            }
+            #[inline(always)]
+            pub fn $virt<const METHOD: &'static str, $($arg_ty,)+ Ret>(self, $($arg: $arg_ty),+) -> Ret {
+                $call_virt_with_self::<ASSEMBLY, CLASS_PATH, false, Generics, METHOD, false, Ret, Self, $($arg_ty),+>(self, $($arg),+)
+            }
+            #[inline(always)]
+            pub fn $ctor<$($arg_ty),+>($($arg: $arg_ty),+) -> Self {
+                $ctor_intrinsic::<ASSEMBLY, CLASS_PATH, false, Generics, $($arg_ty),+>($($arg),+)
+            }
+        )*
+    };
+}
+/// Defines intrinsics calling managed methods with the listed arguments. The codegen reads the argument count from the name of the intrinsic.
+macro_rules! managed_call_intrinsics {
+    ($($call:ident, $call_virt:ident($($arg:ident: $arg_ty:ident),+);)*) => {
+        $(
+            #[allow(unused_variables)]
+            #[inline(never)]
+            pub fn $call<
+                const ASSEMBLY: &'static str,
+                const CLASS_PATH: &'static str,
+                const IS_VALUETYPE: bool,
+                Generics,
+                const METHOD: &'static str,
+                const IS_STATIC: bool,
+                Ret,
+                $($arg_ty),+
+            >($($arg: $arg_ty),+) -> Ret {
+                core::intrinsics::abort();
+            }
+            #[allow(unused_variables)]
+            #[inline(never)]
+            pub fn $call_virt<
+                const ASSEMBLY: &'static str,
+                const CLASS_PATH: &'static str,
+                const IS_VALUETYPE: bool,
+                Generics,
+                const METHOD: &'static str,
+                const IS_STATIC: bool,
+                Ret,
+                $($arg_ty),+
+            >($($arg: $arg_ty),+) -> Ret {
+                core::intrinsics::abort();
+            }
+        )*
+    };
+}
+/// Defines intrinsics creating managed objects using constructors with the listed arguments.
+macro_rules! managed_ctor_intrinsics {
+    ($($ctor:ident($($arg:ident: $arg_ty:ident),*);)*) => {
+        $(
+            #[allow(unused_variables)]
+            #[inline(never)]
+            pub fn $ctor<
+                const ASSEMBLY: &'static str,
+                const CLASS_PATH: &'static str,
+                const IS_VALUETYPE: bool,
+                Generics,
+                $($arg_ty),*
+            >($($arg: $arg_ty),*) -> RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH, Generics> {
+                core::intrinsics::abort();
+            }
+        )*
+    };
+}
+/// A reference to an object of managed class `CLASS_PATH`, with generic arguments `Generics`(a tuple, `(K, V)` for a `Dictionary<K, V>`).
 #[derive(Clone, Copy)]
 #[repr(C)]
-pub struct RustcCLRInteropManagedClass<const ASSEMBLY: &'static str, const CLASS_PATH: &'static str>
-{
+pub struct RustcCLRInteropManagedClass<
+    const ASSEMBLY: &'static str,
+    const CLASS_PATH: &'static str,
+    Generics = (),
+> {
     size_hint: usize,
+    pd: core::marker::PhantomData<Generics>,
 }
-impl<const ASSEMBLY: &'static str, const CLASS_PATH: &'static str>
-    RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH>
+impl<const ASSEMBLY: &'static str, const CLASS_PATH: &'static str, Generics>
+    RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH, Generics>
 {
     #[inline(always)]
     pub fn ctor0() -> Self {
-        rustc_clr_interop_managed_ctor0_::<ASSEMBLY, CLASS_PATH, false>()
+        rustc_clr_interop_managed_ctor0_::<ASSEMBLY, CLASS_PATH, false, Generics>()
     }
     #[inline(always)]
-    pub fn ctor1<Arg1>(arg1: Arg1) -> Self {
-        rustc_clr_interop_managed_ctor1_::<ASSEMBLY, CLASS_PATH, false, Arg1>(arg1)
+    pub fn static0<const METHOD: &'static str, Ret>() -> Ret {
+        rustc_clr_interop_managed_call0_::<ASSEMBLY, CLASS_PATH, false, Generics, METHOD, Ret>()
     }
     #[inline(always)]
-    pub fn ctor2<Arg1, Arg2>(arg1: Arg1, arg2: Arg2) -> Self {
-        rustc_clr_interop_managed_ctor2_::<ASSEMBLY, CLASS_PATH, false, Arg1, Arg2>(arg1, arg2)
+    pub fn instance0<const METHOD: &'static str, Ret>(self) -> Ret {
+        rustc_clr_interop_managed_call1_::<
+            ASSEMBLY,
+            CLASS_PATH,
+            false,
+            Generics,
+            METHOD,
+            false,
+            Ret,
+            Self,
+        >(self)
     }
     #[inline(always)]
-    pub fn ctor3<Arg1, Arg2, Arg3>(arg1: Arg1, arg2: Arg2, arg3: Arg3) -> Self {
-        rustc_clr_interop_managed_ctor3_::<ASSEMBLY, CLASS_PATH, false, Arg1, Arg2, Arg3>(
-            arg1, arg2, arg3,
-        )
+    pub fn virt0<const METHOD: &'static str, Ret>(self) -> Ret {
+        rustc_clr_interop_managed_call_virt1_::<
+            ASSEMBLY,
+            CLASS_PATH,
+            false,
+            Generics,
+            METHOD,
+            false,
+            Ret,
+            Self,
+        >(self)
+    }
+    managed_class_helpers! {
+        static1, instance1, virt1, ctor1 => rustc_clr_interop_managed_call1_, rustc_clr_interop_managed_call2_, rustc_clr_interop_managed_call_virt2_, rustc_clr_interop_managed_ctor1_(arg1: Arg1);
+        static2, instance2, virt2, ctor2 => rustc_clr_interop_managed_call2_, rustc_clr_interop_managed_call3_, rustc_clr_interop_managed_call_virt3_, rustc_clr_interop_managed_ctor2_(arg1: Arg1, arg2: Arg2);
+        static3, instance3, virt3, ctor3 => rustc_clr_interop_managed_call3_, rustc_clr_interop_managed_call4_, rustc_clr_interop_managed_call_virt4_, rustc_clr_interop_managed_ctor3_(arg1: Arg1, arg2: Arg2, arg3: Arg3);
+        static4, instance4, virt4, ctor4 => rustc_clr_interop_managed_call4_, rustc_clr_interop_managed_call5_, rustc_clr_interop_managed_call_virt5_, rustc_clr_interop_managed_ctor4_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4);
+        static5, instance5, virt5, ctor5 => rustc_clr_interop_managed_call5_, rustc_clr_interop_managed_call6_, rustc_clr_interop_managed_call_virt6_, rustc_clr_interop_managed_ctor5_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5);
+        static6, instance6, virt6, ctor6 => rustc_clr_interop_managed_call6_, rustc_clr_interop_managed_call7_, rustc_clr_interop_managed_call_virt7_, rustc_clr_interop_managed_ctor6_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6);
+        static7, instance7, virt7, ctor7 => rustc_clr_interop_managed_call7_, rustc_clr_interop_managed_call8_, rustc_clr_interop_managed_call_virt8_, rustc_clr_interop_managed_ctor7_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7);
+        static8, instance8, virt8, ctor8 => rustc_clr_interop_managed_call8_, rustc_clr_interop_managed_call9_, rustc_clr_interop_managed_call_virt9_, rustc_clr_interop_managed_ctor8_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8);
+        static9, instance9, virt9, ctor9 => rustc_clr_interop_managed_call9_, rustc_clr_interop_managed_call10_, rustc_clr_interop_managed_call_virt10_, rustc_clr_interop_managed_ctor9_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9);
+        static10, instance10, virt10, ctor10 => rustc_clr_interop_managed_call10_, rustc_clr_interop_managed_call11_, rustc_clr_interop_managed_call_virt11_, rustc_clr_interop_managed_ctor10_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10);
+        static11, instance11, virt11, ctor11 => rustc_clr_interop_managed_call11_, rustc_clr_interop_managed_call12_, rustc_clr_interop_managed_call_virt12_, rustc_clr_interop_managed_ctor11_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11);
+        static12, instance12, virt12, ctor12 => rustc_clr_interop_managed_call12_, rustc_clr_interop_managed_call13_, rustc_clr_interop_managed_call_virt13_, rustc_clr_interop_managed_ctor12_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12);
+        static13, instance13, virt13, ctor13 => rustc_clr_interop_managed_call13_, rustc_clr_interop_managed_call14_, rustc_clr_interop_managed_call_virt14_, rustc_clr_interop_managed_ctor13_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13);
+        static14, instance14, virt14, ctor14 => rustc_clr_interop_managed_call14_, rustc_clr_interop_managed_call15_, rustc_clr_interop_managed_call_virt15_, rustc_clr_interop_managed_ctor14_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14);
+        static15, instance15, virt15, ctor15 => rustc_clr_interop_managed_call15_, rustc_clr_interop_managed_call16_, rustc_clr_interop_managed_call_virt16_, rustc_clr_interop_managed_ctor15_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14, arg15: Arg15);
+        static16, instance16, virt16, ctor16 => rustc_clr_interop_managed_call16_, rustc_clr_interop_managed_call17_, rustc_clr_interop_managed_call_virt17_, rustc_clr_interop_managed_ctor16_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14, arg15: Arg15, arg16: Arg16);
     }
+    /// Reads instance field `FIELD` of this object. `T` must match the type of the field: this is not checked at compile time.
     #[inline(always)]
-    pub fn static0<const METHOD: &'static str, Ret>() -> Ret {
-        rustc_clr_interop_managed_call0_::<ASSEMBLY, CLASS_PATH, false, METHOD, Ret>()
+    pub fn get_field<const FIELD: &'static str, T>(self) -> T {
+        rustc_clr_interop_managed_ld_field_::<ASSEMBLY, CLASS_PATH, false, Generics, FIELD, T>(self)
     }
+    /// Sets instance field `FIELD` of this object to `value`.
     #[inline(always)]
-    pub fn instance0<const METHOD: &'static str, Ret>(self) -> Ret {
-        rustc_clr_interop_managed_call1_::<ASSEMBLY, CLASS_PATH, false, METHOD, false, Ret, Self>(
-            self,
+    pub fn set_field<const FIELD: &'static str, T>(self, value: T) {
+        rustc_clr_interop_managed_st_field_::<ASSEMBLY, CLASS_PATH, false, Generics, FIELD, T>(
+            self, value,
         )
     }
+    /// Reads static field `FIELD` of this class.
     #[inline(always)]
-    pub fn virt0<const METHOD: &'static str, Ret>(self) -> Ret {
-        rustc_clr_interop_managed_call_virt1_::<ASSEMBLY, CLASS_PATH, false, METHOD, false, Ret, Self>(
-            self,
+    pub fn get_static_field<const FIELD: &'static str, T>() -> T {
+        rustc_clr_interop_managed_ld_static_field_::<ASSEMBLY, CLASS_PATH, false, Generics, FIELD, T>(
+        )
+    }
+    /// Sets static field `FIELD` of this class to `value`.
+    #[inline(always)]
+    pub fn set_static_field<const FIELD: &'static str, T>(value: T) {
+        rustc_clr_interop_managed_st_static_field_::<ASSEMBLY, CLASS_PATH, false, Generics, FIELD, T>(
+            value,
         )
     }
+    /// Gets the value of instance property `PROPERTY`, by calling its getter(`get_PROPERTY`).
     #[inline(always)]
-    pub fn static1<const METHOD: &'static str, Arg1, Ret>(arg1: Arg1) -> Ret {
-        rustc_clr_interop_managed_call1_::<ASSEMBLY, CLASS_PATH, false, METHOD, true, Ret, Arg1>(
-            arg1,
+    pub fn get_property<const PROPERTY: &'static str, T>(self) -> T {
+        rustc_clr_interop_managed_get_property_::<ASSEMBLY, CLASS_PATH, false, Generics, PROPERTY, T>(
+            self,
         )
     }
+    /// Sets instance property `PROPERTY` to `value`, by calling its setter(`set_PROPERTY`).
     #[inline(always)]
-    pub fn static2<const METHOD: &'static str, Arg1, Arg2, Ret>(arg1: Arg1, arg2: Arg2) -> Ret {
-        rustc_clr_interop_managed_call2_::<ASSEMBLY, CLASS_PATH, false, METHOD, true, Ret, Arg1, Arg2>(
-            arg1, arg2,
+    pub fn set_property<const PROPERTY: &'static str, T>(self, value: T) {
+        rustc_clr_interop_managed_set_property_::<ASSEMBLY, CLASS_PATH, false, Generics, PROPERTY, T>(
+            self, value,
         )
     }
+    /// Gets the value of static property `PROPERTY`, by calling its getter(`get_PROPERTY`).
     #[inline(always)]
-    pub fn instance1<const METHOD: &'static str, Arg1, Ret>(self, arg1: Arg1) -> Ret {
-        rustc_clr_interop_managed_call2_::<
+    pub fn get_static_property<const PROPERTY: &'static str, T>() -> T {
+        rustc_clr_interop_managed_get_static_property_::<
             ASSEMBLY,
             CLASS_PATH,
             false,
-            METHOD,
+            Generics,
+            PROPERTY,
+            T,
+        >()
+    }
+    /// Sets static property `PROPERTY` to `value`, by calling its setter(`set_PROPERTY`).
+    #[inline(always)]
+    pub fn set_static_property<const PROPERTY: &'static str, T>(value: T) {
+        rustc_clr_interop_managed_set_static_property_::<
+            ASSEMBLY,
+            CLASS_PATH,
             false,
-            Ret,
-            Self,
-            Arg1,
-        >(self, arg1)
+            Generics,
+            PROPERTY,
+            T,
+        >(value)
     }
     #[inline(always)]
     pub fn to_mstring(self) -> crate::system::MString {
@@ -76,12 +213,142 @@ impl<const ASSEMBLY: &'static str, const CLASS_PATH: &'static str>
 pub struct RustcCLRInteropManagedChar {
     utf16_char: u16,
 }
+/// A `System.Span<T>`, or a `System.ReadOnlySpan<T>` if `READ_ONLY` is true. Spans can only be passed to managed code, and must not outlive the memory they refer to.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct RustcCLRInteropManagedSpan<T, const READ_ONLY: bool> {
+    ptr: *const T,
+    len: usize,
+}
+/// A managed delegate taking arguments `Args`(a tuple), and returning `Ret`. Delegates returning `()` are `System.Action`s, and all other
+/// delegates are `System.Func`s.
 #[derive(Clone, Copy)]
 #[repr(C)]
-struct RustcCLRInteropManagedArray<T, const DIMENSIONS: usize> {
+pub struct RustcCLRInteropManagedDelegate<Args, Ret> {
+    object_ref: usize,
+    pd: core::marker::PhantomData<(Args, Ret)>,
+}
+/// Marker supertrait of traits whose methods are virtual methods of managed type `TYPE_PATH`(with generic arguments `Generics`, a tuple).
+/// If `IS_INTERFACE` is true, the type is an interface, and Rust types implementing the trait implement that interface. Othervise, the
+/// methods override virtual methods of the type, which can only be `System.Object`. Implemented for all types.
+pub trait RustcCLRInteropVirtuals<
+    const ASSEMBLY: &'static str,
+    const TYPE_PATH: &'static str,
+    const IS_INTERFACE: bool,
+    Generics = (),
+>
+{
+}
+impl<
+        T: ?Sized,
+        const ASSEMBLY: &'static str,
+        const TYPE_PATH: &'static str,
+        const IS_INTERFACE: bool,
+        Generics,
+    > RustcCLRInteropVirtuals<ASSEMBLY, TYPE_PATH, IS_INTERFACE, Generics> for T
+{
+}
+/// A value of generic parameter `INDEX` of a managed class. Arguments and return values of this type make managed methods be called with
+/// the generic parameter in their signature(`List<T>.Add(!0)`), instead of its value(`List<int>.Add(int)`).
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct RustcCLRInteropManagedGenericArg<T, const INDEX: usize> {
+    value: T,
+}
+impl<T, const INDEX: usize> RustcCLRInteropManagedGenericArg<T, INDEX> {
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        rustc_clr_interop_managed_generic_arg_wrap_::<T, INDEX>(value)
+    }
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        rustc_clr_interop_managed_generic_arg_unwrap_::<T, INDEX>(self)
+    }
+}
+/// A managed array(`T[]` or, if `DIMENSIONS` is greater than 1, `T[,]`, `T[,,]`, etc.).
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct RustcCLRInteropManagedArray<T, const DIMENSIONS: usize> {
     object_ref: usize,
     pd: core::marker::PhantomData<T>,
 }
+impl<T: Copy, const DIMENSIONS: usize> RustcCLRInteropManagedArray<T, DIMENSIONS> {
+    /// Creates a new array, with the length of each dimension given by `lengths`.
+    #[inline(always)]
+    pub fn new_with_lengths(lengths: [usize; DIMENSIONS]) -> Self {
+        rustc_clr_interop_managed_array_new_::<T, DIMENSIONS>(lengths.as_ptr())
+    }
+    /// Returns the total number of elements in all dimensions of this array.
+    #[inline(always)]
+    pub fn len(self) -> usize {
+        rustc_clr_interop_managed_array_len_::<T, DIMENSIONS>(self)
+    }
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+    /// Gets the element at `indices`. Throws `IndexOutOfRangeException` if any index is out of bounds.
+    #[inline(always)]
+    pub fn get_at(self, indices: [usize; DIMENSIONS]) -> T {
+        rustc_clr_interop_managed_array_get_::<T, DIMENSIONS>(self, indices.as_ptr())
+    }
+    /// Sets the element at `indices` to `value`. Throws `IndexOutOfRangeException` if any index is out of bounds.
+    #[inline(always)]
+    pub fn set_at(self, indices: [usize; DIMENSIONS], value: T) {
+        rustc_clr_interop_managed_array_set_::<T, DIMENSIONS>(self, indices.as_ptr(), value)
+    }
+    /// Pins this array, so that its elements can be accesed as a Rust slice.
+    pub fn pin(self) -> PinnedArray<T, DIMENSIONS> {
+        PinnedArray {
+            array: self,
+            handle: rustc_clr_interop_gc_handle_pin_::<Self>(self),
+        }
+    }
+}
+impl<T: Copy> RustcCLRInteropManagedArray<T, 1> {
+    /// Creates a new array with `len` elements.
+    #[inline(always)]
+    pub fn new(len: usize) -> Self {
+        Self::new_with_lengths([len])
+    }
+    /// Gets the element at `index`. Throws `IndexOutOfRangeException` if `index` is out of bounds.
+    #[inline(always)]
+    pub fn get(self, index: usize) -> T {
+        self.get_at([index])
+    }
+    /// Sets the element at `index` to `value`. Throws `IndexOutOfRangeException` if `index` is out of bounds.
+    #[inline(always)]
+    pub fn set(self, index: usize, value: T) {
+        self.set_at([index], value)
+    }
+}
+/// A managed array, which will not be moved by the GC until this guard is dropped. Elements of multi-dimensional arrays are laid out in row-major order.
+pub struct PinnedArray<T: Copy, const DIMENSIONS: usize> {
+    array: RustcCLRInteropManagedArray<T, DIMENSIONS>,
+    handle: isize,
+}
+impl<T: Copy, const DIMENSIONS: usize> PinnedArray<T, DIMENSIONS> {
+    fn first_element(&self) -> *mut T {
+        if self.array.is_empty() {
+            return core::ptr::NonNull::dangling().as_ptr();
+        }
+        let indices = [0; DIMENSIONS];
+        rustc_clr_interop_managed_array_address_::<T, DIMENSIONS>(self.array, indices.as_ptr())
+    }
+    /// Returns the elements of the pinned array.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.first_element(), self.array.len()) }
+    }
+    /// Returns the elements of the pinned array.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { core::slice::from_raw_parts_mut(self.first_element(), self.array.len()) }
+    }
+}
+impl<T: Copy, const DIMENSIONS: usize> Drop for PinnedArray<T, DIMENSIONS> {
+    fn drop(&mut self) {
+        rustc_clr_interop_gc_handle_free_(self.handle);
+    }
+}
 //Calls
 #[allow(unused_variables)]
 #[inline(never)]
@@ -89,137 +356,174 @@ pub fn rustc_clr_interop_managed_call0_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
+    Generics,
     const METHOD: &'static str,
     Ret,
 >() -> Ret {
     core::intrinsics::abort();
 }
+//VCalls
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_call1_<
+pub fn rustc_clr_interop_managed_call_virt0_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
+    Generics,
     const METHOD: &'static str,
-    const IS_STATIC: bool,
     Ret,
-    Arg1,
->(
-    arg1: Arg1,
-) -> Ret {
+>() -> Ret {
     core::intrinsics::abort();
 }
+// Instance methods take `self` as their first argument, so calls need one more argument than the helpers.
+managed_call_intrinsics! {
+    rustc_clr_interop_managed_call1_, rustc_clr_interop_managed_call_virt1_(arg1: Arg1);
+    rustc_clr_interop_managed_call2_, rustc_clr_interop_managed_call_virt2_(arg1: Arg1, arg2: Arg2);
+    rustc_clr_interop_managed_call3_, rustc_clr_interop_managed_call_virt3_(arg1: Arg1, arg2: Arg2, arg3: Arg3);
+    rustc_clr_interop_managed_call4_, rustc_clr_interop_managed_call_virt4_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4);
+    rustc_clr_interop_managed_call5_, rustc_clr_interop_managed_call_virt5_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5);
+    rustc_clr_interop_managed_call6_, rustc_clr_interop_managed_call_virt6_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6);
+    rustc_clr_interop_managed_call7_, rustc_clr_interop_managed_call_virt7_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7);
+    rustc_clr_interop_managed_call8_, rustc_clr_interop_managed_call_virt8_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8);
+    rustc_clr_interop_managed_call9_, rustc_clr_interop_managed_call_virt9_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9);
+    rustc_clr_interop_managed_call10_, rustc_clr_interop_managed_call_virt10_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10);
+    rustc_clr_interop_managed_call11_, rustc_clr_interop_managed_call_virt11_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11);
+    rustc_clr_interop_managed_call12_, rustc_clr_interop_managed_call_virt12_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12);
+    rustc_clr_interop_managed_call13_, rustc_clr_interop_managed_call_virt13_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13);
+    rustc_clr_interop_managed_call14_, rustc_clr_interop_managed_call_virt14_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14);
+    rustc_clr_interop_managed_call15_, rustc_clr_interop_managed_call_virt15_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14, arg15: Arg15);
+    rustc_clr_interop_managed_call16_, rustc_clr_interop_managed_call_virt16_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14, arg15: Arg15, arg16: Arg16);
+    rustc_clr_interop_managed_call17_, rustc_clr_interop_managed_call_virt17_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14, arg15: Arg15, arg16: Arg16, arg17: Arg17);
+}
+//Ctors
+managed_ctor_intrinsics! {
+    rustc_clr_interop_managed_ctor0_();
+    rustc_clr_interop_managed_ctor1_(arg1: Arg1);
+    rustc_clr_interop_managed_ctor2_(arg1: Arg1, arg2: Arg2);
+    rustc_clr_interop_managed_ctor3_(arg1: Arg1, arg2: Arg2, arg3: Arg3);
+    rustc_clr_interop_managed_ctor4_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4);
+    rustc_clr_interop_managed_ctor5_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5);
+    rustc_clr_interop_managed_ctor6_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6);
+    rustc_clr_interop_managed_ctor7_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7);
+    rustc_clr_interop_managed_ctor8_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8);
+    rustc_clr_interop_managed_ctor9_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9);
+    rustc_clr_interop_managed_ctor10_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10);
+    rustc_clr_interop_managed_ctor11_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11);
+    rustc_clr_interop_managed_ctor12_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12);
+    rustc_clr_interop_managed_ctor13_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13);
+    rustc_clr_interop_managed_ctor14_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14);
+    rustc_clr_interop_managed_ctor15_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14, arg15: Arg15);
+    rustc_clr_interop_managed_ctor16_(arg1: Arg1, arg2: Arg2, arg3: Arg3, arg4: Arg4, arg5: Arg5, arg6: Arg6, arg7: Arg7, arg8: Arg8, arg9: Arg9, arg10: Arg10, arg11: Arg11, arg12: Arg12, arg13: Arg13, arg14: Arg14, arg15: Arg15, arg16: Arg16);
+}
+//Fields and properties
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_call2_<
+pub fn rustc_clr_interop_managed_ld_field_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
-    const METHOD: &'static str,
-    const IS_STATIC: bool,
-    Ret,
-    Arg1,
-    Arg2,
+    Generics,
+    const FIELD: &'static str,
+    T,
 >(
-    arg1: Arg1,
-    arg2: Arg2,
-) -> Ret {
+    obj: RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH, Generics>,
+) -> T {
     core::intrinsics::abort();
 }
-//VCalls
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_call_virt0_<
+pub fn rustc_clr_interop_managed_st_field_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
-    const METHOD: &'static str,
-    Ret,
->() -> Ret {
+    Generics,
+    const FIELD: &'static str,
+    T,
+>(
+    obj: RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH, Generics>,
+    value: T,
+) {
     core::intrinsics::abort();
 }
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_call_virt1_<
+pub fn rustc_clr_interop_managed_ld_static_field_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
-    const METHOD: &'static str,
-    const IS_STATIC: bool,
-    Ret,
-    Arg1,
->(
-    arg1: Arg1,
-) -> Ret {
+    Generics,
+    const FIELD: &'static str,
+    T,
+>() -> T {
     core::intrinsics::abort();
 }
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_call_virt2_<
+pub fn rustc_clr_interop_managed_st_static_field_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
-    const METHOD: &'static str,
-    const IS_STATIC: bool,
-    Ret,
-    Arg1,
-    Arg2,
+    Generics,
+    const FIELD: &'static str,
+    T,
 >(
-    arg1: Arg1,
-    arg2: Arg2,
-) -> Ret {
+    value: T,
+) {
     core::intrinsics::abort();
 }
-//Ctors
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_ctor0_<
+pub fn rustc_clr_interop_managed_get_property_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
->() -> RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH> {
+    Generics,
+    const PROPERTY: &'static str,
+    T,
+>(
+    obj: RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH, Generics>,
+) -> T {
     core::intrinsics::abort();
 }
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_ctor1_<
+pub fn rustc_clr_interop_managed_set_property_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
-    Arg1,
+    Generics,
+    const PROPERTY: &'static str,
+    T,
 >(
-    arg1: Arg1,
-) -> RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH> {
+    obj: RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH, Generics>,
+    value: T,
+) {
     core::intrinsics::abort();
 }
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_ctor2_<
+pub fn rustc_clr_interop_managed_get_static_property_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
-    Arg1,
-    Arg2,
->(
-    arg1: Arg1,
-    arg2: Arg2,
-) -> RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH> {
+    Generics,
+    const PROPERTY: &'static str,
+    T,
+>() -> T {
     core::intrinsics::abort();
 }
 #[allow(unused_variables)]
 #[inline(never)]
-pub fn rustc_clr_interop_managed_ctor3_<
+pub fn rustc_clr_interop_managed_set_static_property_<
     const ASSEMBLY: &'static str,
     const CLASS_PATH: &'static str,
     const IS_VALUETYPE: bool,
-    Arg1,
-    Arg2,
-    Arg3,
+    Generics,
+    const PROPERTY: &'static str,
+    T,
 >(
-    arg1: Arg1,
-    arg2: Arg2,
-    arg3: Arg3,
-) -> RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH> {
+    value: T,
+) {
     core::intrinsics::abort();
 }
 impl From<u16> for RustcCLRInteropManagedChar {
@@ -300,3 +604,130 @@ impl RustcCLRInteropManagedChar {
         }
     }
 }
+//GC handles
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_gc_handle_alloc_<T>(obj: T) -> isize {
+    core::intrinsics::abort();
+}
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_gc_handle_target_<T>(handle: isize) -> T {
+    core::intrinsics::abort();
+}
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_gc_handle_free_(handle: isize) {
+    core::intrinsics::abort();
+}
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_gc_handle_pin_<T>(obj: T) -> isize {
+    core::intrinsics::abort();
+}
+//Arrays
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_managed_array_new_<T, const DIMENSIONS: usize>(
+    lengths: *const usize,
+) -> RustcCLRInteropManagedArray<T, DIMENSIONS> {
+    core::intrinsics::abort();
+}
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_managed_array_len_<T, const DIMENSIONS: usize>(
+    array: RustcCLRInteropManagedArray<T, DIMENSIONS>,
+) -> usize {
+    core::intrinsics::abort();
+}
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_managed_array_get_<T, const DIMENSIONS: usize>(
+    array: RustcCLRInteropManagedArray<T, DIMENSIONS>,
+    indices: *const usize,
+) -> T {
+    core::intrinsics::abort();
+}
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_managed_array_set_<T, const DIMENSIONS: usize>(
+    array: RustcCLRInteropManagedArray<T, DIMENSIONS>,
+    indices: *const usize,
+    value: T,
+) {
+    core::intrinsics::abort();
+}
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_managed_array_address_<T, const DIMENSIONS: usize>(
+    array: RustcCLRInteropManagedArray<T, DIMENSIONS>,
+    indices: *const usize,
+) -> *mut T {
+    core::intrinsics::abort();
+}
+//Spans
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_managed_span_new_<T, const READ_ONLY: bool>(
+    ptr: *const T,
+    len: usize,
+) -> RustcCLRInteropManagedSpan<T, READ_ONLY> {
+    core::intrinsics::abort();
+}
+//Exceptions
+/// Calls `f` inside a protected region, catching exceptions of class `CLASS_PATH`. A caught exception is returned as a `GCHandle`.
+#[inline(never)]
+pub fn rustc_clr_interop_try_catch_<
+    const ASSEMBLY: &'static str,
+    const CLASS_PATH: &'static str,
+    T,
+    F: FnOnce() -> T,
+>(
+    f: F,
+) -> Result<T, isize> {
+    // The codegen replaces calls to this function, but the closure still must be called here, so that its code is generated.
+    Ok(f())
+}
+//Generic arguments
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_managed_generic_arg_wrap_<T, const INDEX: usize>(
+    value: T,
+) -> RustcCLRInteropManagedGenericArg<T, INDEX> {
+    core::intrinsics::abort();
+}
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_managed_generic_arg_unwrap_<T, const INDEX: usize>(
+    arg: RustcCLRInteropManagedGenericArg<T, INDEX>,
+) -> T {
+    core::intrinsics::abort();
+}
+//Delegates
+/// Creates a delegate calling the function behind `fn_ptr`.
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_delegate_from_fn_ptr_<Args, Ret, FnPtr: Copy>(
+    fn_ptr: FnPtr,
+) -> RustcCLRInteropManagedDelegate<Args, Ret> {
+    core::intrinsics::abort();
+}
+/// Creates a delegate calling the function behind `thunk`, with `env` stored in the delegate target, passed as the first argument.
+/// `drop_env(env)` is called when the target is finalized.
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_delegate_new_<Args, Ret, FnPtr: Copy>(
+    env: *const (),
+    drop_env: unsafe fn(*mut ()),
+    thunk: FnPtr,
+) -> RustcCLRInteropManagedDelegate<Args, Ret> {
+    core::intrinsics::abort();
+}
+/// Gets the environment pointer from the target of a delegate created by [`rustc_clr_interop_delegate_new_`].
+#[allow(unused_variables)]
+#[inline(never)]
+pub fn rustc_clr_interop_delegate_env_(
+    target: RustcCLRInteropManagedClass<"System.Runtime", "System.Object">,
+) -> *const () {
+    core::intrinsics::abort();
+}
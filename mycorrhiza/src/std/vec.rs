@@ -15,7 +15,40 @@ impl<T> Drop for Vec<T> {
         }
     }
 }
+impl<T> Default for Vec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl<T> Vec<T> {
+    /// Creates a new, empty vector.
+    pub fn new() -> Self {
+        Self {
+            buf: ptr::null_mut(),
+            cap: 0,
+            len: 0,
+        }
+    }
+    /// Creates a new, empty vector with space for at least `cap` elements.
+    pub fn with_capacity(cap: usize) -> Self {
+        let mut vec = Self::new();
+        if cap > 0 {
+            unsafe { vec.resize_buff_to(cap) };
+        }
+        vec
+    }
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf
+    }
+    /// Sets the length of this vector.
+    /// # Safety
+    /// `new_len` must not exceed the capacity, and the first `new_len` elements must be initialized.
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        self.len = new_len;
+    }
     /// Resizes the internal buffer to have the capacity for exactly `new_cap` elements. WARNING: This does not call drop nor initalizes the memory.
     unsafe fn resize_buff_to(&mut self, new_cap: usize) {
         let new_cap_in_bytes = new_cap * core::mem::size_of::<T>();
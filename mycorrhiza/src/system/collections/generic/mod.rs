@@ -0,0 +1,117 @@
+use crate::intrinsics::{RustcCLRInteropManagedClass, RustcCLRInteropManagedGenericArg};
+/// `System.Collections.Generic.List<T>`. `T` must have a managed equivalent(a primitive, or a managed class).
+pub type List<T> =
+    RustcCLRInteropManagedClass<"System.Collections", "System.Collections.Generic.List`1", (T,)>;
+/// `System.Collections.Generic.Dictionary<K, V>`. `K` and `V` must have managed equivalents(primitives, or managed classes).
+pub type Dictionary<K, V> = RustcCLRInteropManagedClass<
+    "System.Collections",
+    "System.Collections.Generic.Dictionary`2",
+    (K, V),
+>;
+impl<T: Copy> List<T> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::ctor0()
+    }
+    #[inline(always)]
+    pub fn with_capacity(capacity: i32) -> Self {
+        Self::ctor1::<i32>(capacity)
+    }
+    #[inline(always)]
+    pub fn len(self) -> i32 {
+        self.get_property::<"Count", i32>()
+    }
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+    #[inline(always)]
+    pub fn push(self, item: T) {
+        self.instance1::<"Add", RustcCLRInteropManagedGenericArg<T, 0>, ()>(
+            RustcCLRInteropManagedGenericArg::new(item),
+        )
+    }
+    /// Gets the element at `index`. Throws `ArgumentOutOfRangeException` if `index` is out of bounds.
+    #[inline(always)]
+    pub fn get(self, index: i32) -> T {
+        self.instance1::<"get_Item", i32, RustcCLRInteropManagedGenericArg<T, 0>>(index)
+            .into_inner()
+    }
+    /// Sets the element at `index` to `item`. Throws `ArgumentOutOfRangeException` if `index` is out of bounds.
+    #[inline(always)]
+    pub fn set(self, index: i32, item: T) {
+        self.instance2::<"set_Item", i32, RustcCLRInteropManagedGenericArg<T, 0>, ()>(
+            index,
+            RustcCLRInteropManagedGenericArg::new(item),
+        )
+    }
+    #[inline(always)]
+    pub fn contains(self, item: T) -> bool {
+        self.instance1::<"Contains", RustcCLRInteropManagedGenericArg<T, 0>, bool>(
+            RustcCLRInteropManagedGenericArg::new(item),
+        )
+    }
+    /// Removes the element at `index`. Throws `ArgumentOutOfRangeException` if `index` is out of bounds.
+    #[inline(always)]
+    pub fn remove_at(self, index: i32) {
+        self.instance1::<"RemoveAt", i32, ()>(index)
+    }
+    #[inline(always)]
+    pub fn clear(self) {
+        self.instance0::<"Clear", ()>()
+    }
+}
+impl<K: Copy, V: Copy> Dictionary<K, V> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::ctor0()
+    }
+    #[inline(always)]
+    pub fn len(self) -> i32 {
+        self.get_property::<"Count", i32>()
+    }
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+    /// Sets the value of `key` to `value`, replacing the previous value, if any.
+    #[inline(always)]
+    pub fn insert(self, key: K, value: V) {
+        self.instance2::<
+            "set_Item",
+            RustcCLRInteropManagedGenericArg<K, 0>,
+            RustcCLRInteropManagedGenericArg<V, 1>,
+            (),
+        >(
+            RustcCLRInteropManagedGenericArg::new(key),
+            RustcCLRInteropManagedGenericArg::new(value),
+        )
+    }
+    /// Gets the value of `key`. Throws `KeyNotFoundException` if there is no such key.
+    #[inline(always)]
+    pub fn get(self, key: K) -> V {
+        self.instance1::<
+            "get_Item",
+            RustcCLRInteropManagedGenericArg<K, 0>,
+            RustcCLRInteropManagedGenericArg<V, 1>,
+        >(RustcCLRInteropManagedGenericArg::new(key))
+        .into_inner()
+    }
+    #[inline(always)]
+    pub fn contains_key(self, key: K) -> bool {
+        self.instance1::<"ContainsKey", RustcCLRInteropManagedGenericArg<K, 0>, bool>(
+            RustcCLRInteropManagedGenericArg::new(key),
+        )
+    }
+    /// Removes `key` and its value. Returns false if there was no such key.
+    #[inline(always)]
+    pub fn remove(self, key: K) -> bool {
+        self.instance1::<"Remove", RustcCLRInteropManagedGenericArg<K, 0>, bool>(
+            RustcCLRInteropManagedGenericArg::new(key),
+        )
+    }
+    #[inline(always)]
+    pub fn clear(self) {
+        self.instance0::<"Clear", ()>()
+    }
+}
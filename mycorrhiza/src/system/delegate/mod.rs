@@ -0,0 +1,52 @@
+use crate::intrinsics::{
+    rustc_clr_interop_delegate_env_, rustc_clr_interop_delegate_from_fn_ptr_,
+    rustc_clr_interop_delegate_new_, RustcCLRInteropManagedClass, RustcCLRInteropManagedDelegate,
+};
+type Object = RustcCLRInteropManagedClass<"System.Runtime", "System.Object">;
+type Marshal = RustcCLRInteropManagedClass<
+    "System.Runtime.InteropServices",
+    "System.Runtime.InteropServices.Marshal",
+>;
+/// A `System.Action` delegate, taking arguments `Args`(a tuple).
+pub type Action<Args> = RustcCLRInteropManagedDelegate<Args, ()>;
+/// A `System.Func` delegate, taking arguments `Args`(a tuple) and returning `Ret`.
+pub type Func<Args, Ret> = RustcCLRInteropManagedDelegate<Args, Ret>;
+/// Defines constructors of delegates with the listed arguments, and the thunk calling closures from such delegates.
+macro_rules! delegate_ctors {
+    ($($thunk:ident($($arg:ident: $arg_ty:ident),*);)*) => {
+        $(
+            impl<$($arg_ty,)* Ret> RustcCLRInteropManagedDelegate<($($arg_ty,)*), Ret> {
+                /// Creates a delegate calling `f`.
+                pub fn from_fn(f: fn($($arg_ty),*) -> Ret) -> Self {
+                    rustc_clr_interop_delegate_from_fn_ptr_::<($($arg_ty,)*), Ret, _>(f)
+                }
+                /// Creates a delegate calling closure `f`. The closure is moved to unmanaged memory, and is dropped(on the finalizer
+                /// thread) once the GC finalizes the delegate target.
+                pub fn new<F: Fn($($arg_ty),*) -> Ret + 'static>(f: F) -> Self {
+                    let env = Marshal::static1::<"AllocHGlobal", isize, isize>(core::mem::size_of::<F>() as isize)
+                        as *mut F;
+                    unsafe { env.write(f) };
+                    let thunk: fn(Object, $($arg_ty),*) -> Ret = $thunk::<F, $($arg_ty,)* Ret>;
+                    rustc_clr_interop_delegate_new_::<($($arg_ty,)*), Ret, _>(env as *const (), drop_env::<F>, thunk)
+                }
+            }
+            /// Calls the closure whose adress is boxed in `target`.
+            fn $thunk<F: Fn($($arg_ty),*) -> Ret, $($arg_ty,)* Ret>(target: Object, $($arg: $arg_ty),*) -> Ret {
+                let env = rustc_clr_interop_delegate_env_(target) as *const F;
+                unsafe { (*env)($($arg),*) }
+            }
+        )*
+    };
+}
+/// Drops the closure at `env`, and frees the memory it was allocated in.
+unsafe fn drop_env<F>(env: *mut ()) {
+    core::ptr::drop_in_place(env as *mut F);
+    Marshal::static1::<"FreeHGlobal", isize, ()>(env as isize);
+}
+delegate_ctors! {
+    closure_thunk0();
+    closure_thunk1(a1: A1);
+    closure_thunk2(a1: A1, a2: A2);
+    closure_thunk3(a1: A1, a2: A2, a3: A3);
+    closure_thunk4(a1: A1, a2: A2, a3: A3, a4: A4);
+}
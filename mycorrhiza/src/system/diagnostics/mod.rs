@@ -1,6 +1,6 @@
 pub type Stopwatch = crate::intrinsics::RustcCLRInteropManagedClass<
     "System.Runtime",
-    "System.Diagnostics.Stopwatch ",
+    "System.Diagnostics.Stopwatch",
 >;
 impl Stopwatch {
     #[inline(always)]
@@ -25,6 +25,6 @@ impl Stopwatch {
     }
     #[inline(always)]
     pub fn elapsed_milliseconds(self) -> i64 {
-        Self::virt0::<"get_ElapsedMilliseconds", i64>(self)
+        self.get_property::<"ElapsedMilliseconds", i64>()
     }
 }
@@ -0,0 +1,42 @@
+use crate::intrinsics::{rustc_clr_interop_try_catch_, RustcCLRInteropManagedClass};
+use crate::system::runtime::interop_services::GCHandle;
+use crate::system::MString;
+pub type Exception = RustcCLRInteropManagedClass<"System.Runtime", "System.Exception">;
+/// A managed exception of class `CLASS_PATH`, caught by [`try_managed`] or [`try_catch`].
+pub struct ManagedException<const ASSEMBLY: &'static str, const CLASS_PATH: &'static str> {
+    handle: GCHandle<RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH>>,
+}
+impl<const ASSEMBLY: &'static str, const CLASS_PATH: &'static str>
+    ManagedException<ASSEMBLY, CLASS_PATH>
+{
+    /// Returns the caught exception object.
+    pub fn exception(&self) -> RustcCLRInteropManagedClass<ASSEMBLY, CLASS_PATH> {
+        self.handle.target()
+    }
+    /// Returns the message describing the exception.
+    pub fn message(&self) -> MString {
+        self.exception().get_property::<"Message", MString>()
+    }
+}
+/// Runs `f`, catching any managed exception it throws. Rust panics are not caught, and keep unwinding.
+pub fn try_managed<T, F: FnOnce() -> T>(
+    f: F,
+) -> Result<T, ManagedException<"System.Runtime", "System.Exception">> {
+    try_catch::<"System.Runtime", "System.Exception", T, F>(f)
+}
+/// Runs `f`, catching managed exceptions of class `CLASS_PATH`(or classes derived from it). Other exceptions are not caught.
+pub fn try_catch<
+    const ASSEMBLY: &'static str,
+    const CLASS_PATH: &'static str,
+    T,
+    F: FnOnce() -> T,
+>(
+    f: F,
+) -> Result<T, ManagedException<ASSEMBLY, CLASS_PATH>> {
+    rustc_clr_interop_try_catch_::<ASSEMBLY, CLASS_PATH, T, F>(f).map_err(|handle| {
+        ManagedException {
+            // The codegen allocates a new handle for each caught exception, so it is owned by the `ManagedException`.
+            handle: unsafe { GCHandle::from_raw(handle) },
+        }
+    })
+}
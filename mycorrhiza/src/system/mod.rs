@@ -1,6 +1,111 @@
+pub mod collections;
 pub mod console;
+pub mod delegate;
 pub mod diagnostics;
+pub mod exception;
 pub mod runtime;
 pub mod text;
 pub type MString =
     crate::intrinsics::RustcCLRInteropManagedClass<"System.Runtime", "System.String">;
+pub type Span<T> = crate::intrinsics::RustcCLRInteropManagedSpan<T, false>;
+pub type ReadOnlySpan<T> = crate::intrinsics::RustcCLRInteropManagedSpan<T, true>;
+/// `System.IDisposable`. Rust types implementing it can be disposed by managed code.
+pub trait IDisposable:
+    crate::intrinsics::RustcCLRInteropVirtuals<"System.Runtime", "System.IDisposable", true>
+{
+    fn dispose(&mut self);
+}
+/// `System.IComparable<T>`. Rust types implementing it can be compared(eg. sorted) by managed code.
+pub trait IComparable<T>:
+    crate::intrinsics::RustcCLRInteropVirtuals<"System.Runtime", "System.IComparable`1", true, (T,)>
+{
+    fn compare_to(&self, other: T) -> i32;
+}
+/// Overrides `System.Object.ToString`, which is used when managed code converts Rust types to strings.
+pub trait ToMString:
+    crate::intrinsics::RustcCLRInteropVirtuals<"System.Runtime", "System.Object", false>
+{
+    fn to_string(&self) -> MString;
+}
+type Encoding =
+    crate::intrinsics::RustcCLRInteropManagedClass<"System.Runtime", "System.Text.Encoding">;
+impl<T: Copy> Span<T> {
+    /// Creates a span refering to the elements of `slice`. The span must not be used after `slice` is dropped.
+    #[inline(always)]
+    pub fn from_slice(slice: &mut [T]) -> Self {
+        crate::intrinsics::rustc_clr_interop_managed_span_new_::<T, false>(
+            slice.as_ptr(),
+            slice.len(),
+        )
+    }
+}
+impl<T: Copy> ReadOnlySpan<T> {
+    /// Creates a read-only span refering to the elements of `slice`. The span must not be used after `slice` is dropped.
+    #[inline(always)]
+    pub fn from_slice(slice: &[T]) -> Self {
+        crate::intrinsics::rustc_clr_interop_managed_span_new_::<T, true>(
+            slice.as_ptr(),
+            slice.len(),
+        )
+    }
+}
+impl MString {
+    /// Creates a new managed string, decoding the UTF-8 encoded `bytes`. Invalid UTF-8 sequences are replaced with U+FFFD.
+    pub fn from_utf8(bytes: &[u8]) -> Self {
+        let len: i32 = bytes
+            .len()
+            .try_into()
+            .expect("String too long to be managed!");
+        Encoding::get_static_property::<"UTF8", Encoding>()
+            .virt2::<"GetString", *const u8, i32, Self>(bytes.as_ptr(), len)
+    }
+    /// Creates a new managed string from `string`. The codegen does not lower `&str` arguments yet, so [`Self::from_utf8`] should be
+    /// preferred until it does.
+    pub fn from_str(string: &str) -> Self {
+        Self::from_utf8(string.as_bytes())
+    }
+    /// Returns the length of this string, in UTF-16 code units.
+    pub fn len(self) -> usize {
+        self.get_property::<"Length", i32>() as usize
+    }
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the length of this string, encoded as UTF-8.
+    pub fn utf8_len(self) -> usize {
+        Encoding::get_static_property::<"UTF8", Encoding>().virt1::<"GetByteCount", Self, i32>(self)
+            as usize
+    }
+    /// Copies the UTF-16 code units of this string into `buf`, which must be at least [`Self::len`] elements long.
+    pub fn copy_to_utf16(self, buf: &mut [u16]) {
+        assert!(buf.len() >= self.len());
+        let span = crate::intrinsics::rustc_clr_interop_managed_span_new_::<crate::DotNetChar, false>(
+            buf.as_mut_ptr() as *const crate::DotNetChar,
+            buf.len(),
+        );
+        self.instance1::<"CopyTo", Span<crate::DotNetChar>, ()>(span)
+    }
+    /// Encodes this string as UTF-8 into `buf`, and returns the number of bytes written. `buf` must be at least [`Self::utf8_len`] bytes long.
+    pub fn copy_to_utf8(self, buf: &mut [u8]) -> usize {
+        unsafe { self.copy_to_utf8_raw(buf.as_mut_ptr(), buf.len()) }
+    }
+    /// Encodes this string as UTF-8 into the `len` bytes at `buf`, and returns the number of bytes written.
+    /// # Safety
+    /// `buf` must be valid for writes of `len` bytes. The bytes don't need to be initialized.
+    unsafe fn copy_to_utf8_raw(self, buf: *mut u8, len: usize) -> usize {
+        let chars = Self::static1::<"op_Implicit", Self, ReadOnlySpan<crate::DotNetChar>>(self);
+        let span = crate::intrinsics::rustc_clr_interop_managed_span_new_::<u8, false>(buf, len);
+        Encoding::get_static_property::<"UTF8", Encoding>()
+            .virt2::<"GetBytes", ReadOnlySpan<crate::DotNetChar>, Span<u8>, i32>(chars, span)
+            as usize
+    }
+    /// Converts this string into a Rust-owned, UTF-8 encoded buffer.
+    pub fn to_utf8_vec(self) -> crate::std::vec::Vec<u8> {
+        let len = self.utf8_len();
+        let mut vec = crate::std::vec::Vec::with_capacity(len);
+        // The spare capacity is written through a raw pointer, since it is not initialized yet.
+        let written = unsafe { self.copy_to_utf8_raw(vec.as_mut_ptr(), len) };
+        unsafe { vec.set_len(written) };
+        vec
+    }
+}
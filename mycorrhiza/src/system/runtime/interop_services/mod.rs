@@ -1 +1,51 @@
-
+use crate::intrinsics::{
+    rustc_clr_interop_gc_handle_alloc_, rustc_clr_interop_gc_handle_free_,
+    rustc_clr_interop_gc_handle_target_,
+};
+use core::marker::PhantomData;
+/// An owned `System.Runtime.InteropServices.GCHandle`, which keeps managed object `T` alive, and can be stored in unmanaged memory.
+/// The handle is freed when dropped.
+pub struct GCHandle<T: Copy> {
+    handle: isize,
+    pd: PhantomData<T>,
+}
+impl<T: Copy> GCHandle<T> {
+    /// Allocates a new handle to managed object `obj`.
+    #[inline(always)]
+    pub fn new(obj: T) -> Self {
+        Self {
+            handle: rustc_clr_interop_gc_handle_alloc_::<T>(obj),
+            pd: PhantomData,
+        }
+    }
+    /// Gets the object this handle refers to.
+    #[inline(always)]
+    pub fn target(&self) -> T {
+        rustc_clr_interop_gc_handle_target_::<T>(self.handle)
+    }
+    /// Converts this handle into the underlying `IntPtr`, without freeing it.
+    pub fn into_raw(self) -> isize {
+        let handle = self.handle;
+        core::mem::forget(self);
+        handle
+    }
+    /// Takes ownership of a handle previously returned by [`Self::into_raw`].
+    /// # Safety
+    /// `handle` must be a valid handle to an object of type `T`, and must not be freed elsewhere.
+    pub unsafe fn from_raw(handle: isize) -> Self {
+        Self {
+            handle,
+            pd: PhantomData,
+        }
+    }
+}
+impl<T: Copy> Clone for GCHandle<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.target())
+    }
+}
+impl<T: Copy> Drop for GCHandle<T> {
+    fn drop(&mut self) {
+        rustc_clr_interop_gc_handle_free_(self.handle);
+    }
+}
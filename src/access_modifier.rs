@@ -6,14 +6,6 @@ pub enum AccessModifer {
     Private,
     /// Specifies that the givem item is public
     Public,
-}
-impl AccessModifer {
-    /// Converts Rust visibilty type to [`AccessModifier`]
-    pub fn from_visibility<T>(visibility: rustc_middle::ty::Visibility<T>) -> Self {
-        if let rustc_middle::ty::Visibility::Public = visibility {
-            Self::Public
-        } else {
-            Self::Private
-        }
-    }
+    /// Specifies that the given item is only accessible from its type, and types derived from it(`protected` in C#)
+    Family,
 }
@@ -115,7 +115,8 @@ pub fn handle_aggregate<'tyctx>(
     }
 }
 /// Builds an Algebraic Data Type (struct,enum,union) at location `target_location`, with fields set using ops in `fields`.
-fn aggregate_adt<'tyctx>(
+/// Creates variant `variant_idx` of ADT `adt_type` at `target_location`, with fields set to values calculated by `fields`. Leaves the created value on the stack.
+pub(crate) fn aggregate_adt<'tyctx>(
     tyctx: TyCtxt<'tyctx>,
     target_location: &Place<'tyctx>,
     method: &rustc_middle::mir::Body<'tyctx>,
@@ -1,13 +1,17 @@
+use crate::assembly_exporter::AssemblyInfo;
 use crate::cil_op::{CILOp, CallSite};
 use crate::codegen_error::MethodCodegenError;
 use crate::utilis::monomorphize;
 use crate::{
     access_modifier::AccessModifer, codegen_error::CodegenError, function_sig::FnSig,
-    method::Method, r#type::Type, type_def::TypeDef,
+    method::Method, r#type::Type, type_def::TypeDef, IString,
 };
-use rustc_middle::mir::{mono::MonoItem,Body, Local, LocalDecl,Statement};
-use rustc_middle::ty::{Instance, ParamEnv, TyCtxt};
-use std::collections::HashSet;
+use rustc_hir::def::DefKind;
+use rustc_middle::mir::{mono::MonoItem,Body, Local, LocalDecl,Statement, VarDebugInfoContents};
+use rustc_middle::ty::{Instance, InstanceDef, ParamEnv, TyCtxt};
+use rustc_session::config::DebugInfo;
+use rustc_span::{symbol::kw, Span};
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 
 use serde::{Deserialize, Serialize};
@@ -15,7 +19,11 @@ use serde::{Deserialize, Serialize};
 pub struct Assembly {
     types: HashSet<TypeDef>,
     functions: HashSet<Method>,
+    /// Symbols of functions skipped because they have no MIR. Calls to them fail at runtime, but are not link errors.
+    no_mir: HashSet<IString>,
     entrypoint: Option<CallSite>,
+    /// Name and version of the assembly. If not set, a default one is used.
+    info: Option<AssemblyInfo>,
 }
 impl Assembly {
     /// Creates a new, empty assembly.
@@ -23,20 +31,35 @@ impl Assembly {
         Self {
             types: HashSet::new(),
             functions: HashSet::new(),
+            no_mir: HashSet::new(),
             entrypoint: None,
+            info: None,
         }
     }
     /// Joins 2 assemblies together.
     pub fn join(self, other: Self) -> Self {
         let types = self.types.union(&other.types).cloned().collect();
         let functions = self.functions.union(&other.functions).cloned().collect();
+        let no_mir = self.no_mir.union(&other.no_mir).cloned().collect();
         let entrypoint = self.entrypoint.or(other.entrypoint);
+        // The first assembly is the one being linked, so its name is used.
+        let info = self.info.or(other.info);
         Self {
             types,
             functions,
+            no_mir,
             entrypoint,
+            info,
         }
     }
+    /// Returns the name and version of the assembly, if set.
+    pub fn info(&self) -> Option<&AssemblyInfo> {
+        self.info.as_ref()
+    }
+    /// Sets the name and version of the assembly.
+    pub fn set_info(&mut self, info: AssemblyInfo) {
+        self.info = Some(info);
+    }
     /// Adds a rust MIR function to the assembly.
     pub fn add_fn<'tcx>(
         &mut self,
@@ -50,28 +73,47 @@ impl Assembly {
         // Get the MIR if it exisits. Othervise, return early.
         if !tcx.is_mir_available(instance.def_id()) {
             println!("function {instance:?} has no MIR. Skippping.");
+            self.no_mir.insert(name.into());
             return Ok(());
         }
         let mir = tcx.optimized_mir(instance.def_id());
         // TODO: check if this is OK. It seems to work for now, but there may be some edge cases.
         let param_env = ParamEnv::empty();
-        // Check if function is public or not.
-        let access_modifier = AccessModifer::from_visibility(tcx.visibility(instance.def_id()));
+        // Functions are only exposed to other assemblies through exports(see `crate::exports`), so they are always private.
+        let access_modifier = AccessModifer::Private;
         // Handle the function signature
         let sig = FnSig::from_poly_sig(&instance.ty(tcx, param_env).fn_sig(tcx), tcx, &instance)?;
         // Get locals
         let locals = locals_from_mir(&mir.local_decls, tcx, sig.inputs().len(), &instance);
+        let argc = sig.inputs().len();
         // Create method prototype
         let mut method = Method::new(access_modifier, true, sig, name, locals);
+        let (arg_names, local_names) = debug_names(mir, tcx, &instance, argc);
+        method.set_arg_names(arg_names);
+        method.set_local_names(local_names);
         let mut ops = Vec::new();
         let mut last_bb_id = 0;
 
         let blocks = &(*mir.basic_blocks);
-
+        // Labels past the last basic block are free to be used by terminators.
+        let mut next_label = blocks.len() as u32;
+        let emit_source_info = tcx.sess.opts.debuginfo != DebugInfo::None;
+        let mut last_source_info = None;
+        let mut mark_source = |ops: &mut Vec<CILOp>, span: Span| {
+            if !emit_source_info {
+                return;
+            }
+            let source_info = source_info_op(tcx, mir, span);
+            if source_info.is_some() && source_info != last_source_info {
+                ops.extend(source_info.clone());
+                last_source_info = source_info;
+            }
+        };
         for block_data in blocks {
             ops.push(CILOp::Label(last_bb_id));
             last_bb_id += 1;
             for statement in &block_data.statements {
+                mark_source(&mut ops, statement.source_info.span);
                 if crate::INSERT_MIR_DEBUG_COMMENTS {
                     ops.push(CILOp::Comment(format!("{statement:?}").into()));
                 }
@@ -86,9 +128,17 @@ impl Assembly {
                 //println!("ops:{ops:?}\n\n");
             }
             match &block_data.terminator {
-                Some(term) => ops.extend(crate::terminator::handle_terminator(
-                    term, mir, tcx, mir, instance,
-                )),
+                Some(term) => {
+                    mark_source(&mut ops, term.source_info.span);
+                    ops.extend(crate::terminator::handle_terminator(
+                        term,
+                        mir,
+                        tcx,
+                        mir,
+                        instance,
+                        &mut next_label,
+                    ))
+                }
                 None => (),
             }
         }
@@ -123,26 +173,125 @@ impl Assembly {
         method: &Instance<'tyctx>,
     ) {
         for type_def in TypeDef::from_ty(ty, tyctx, method) {
-            self.types.insert(type_def);
+            self.add_typedef_if_missing(type_def);
         }
     }
-    /// Optimizes all the methods witin the assembly.
+    /// Adds `type_def`, unless a type with the same name is already present. Types are compared by name, since the present type may
+    /// have been extended(with exported or virtual methods), and so differ from a freshly created one.
+    fn add_typedef_if_missing(&mut self, type_def: TypeDef) {
+        if self.types.contains(&type_def)
+            || self.types.iter().any(|tpe| tpe.name() == type_def.name())
+        {
+            return;
+        }
+        self.types.insert(type_def);
+    }
+    /// Optimizes all the methods witin the assembly. The amount of optimization fuel and disabled passes are read from the enviroment, see [`crate::opt::OptFuel`].
     pub fn opt(&mut self) {
-        let functions: HashSet<_> = self
-            .functions
-            .iter()
+        let mut fuel = crate::opt::OptFuel::from_env();
+        // Methods are optimized in a fixed order, so that the same amount of fuel always results in the same rewrites.
+        let mut methods: Vec<_> = self.functions.iter().collect();
+        methods.sort_by(|a, b| a.name().cmp(b.name()));
+        let functions: HashSet<_> = methods
+            .into_iter()
             .map(|method| {
                 let mut method = method.clone();
-                crate::opt::opt_method(&mut method);
+                crate::opt::opt_method(&mut method, &mut fuel);
                 method
             })
             .collect();
         self.functions = functions;
     }
+    /// Removes all methods and types not reachable from the entrypoint. If `keep_public` is true, public methods and types are kept too, since
+    /// they can be used by other assemblies.
+    pub fn eliminate_dead_code(&mut self, keep_public: bool) {
+        let mut reachable = crate::dead_code::Reachable::default();
+        if let Some(entrypoint) = &self.entrypoint {
+            reachable.add_method(entrypoint.name());
+        }
+        for method in &self.functions {
+            if method.is_entrypoint() || (keep_public && method.access() == AccessModifer::Public) {
+                reachable.add_method(method.name());
+            }
+        }
+        if keep_public {
+            for tpe in &self.types {
+                if tpe.access_modifier() == AccessModifer::Public {
+                    reachable.add_type_name(tpe.name());
+                }
+            }
+        }
+        reachable.walk(self.functions.iter(), self.types.iter());
+        self.functions
+            .retain(|method| reachable.has_method(method.name()));
+        self.types.retain(|tpe| reachable.has_type(tpe.name()));
+    }
+    /// Removes P/Invoke declarations of methods which also have a managed implementation(eg. libc functions emulated using .NET APIs).
+    pub fn remove_shadowed_pinvokes(&mut self) {
+        let implemented: HashSet<crate::IString> = self
+            .functions
+            .iter()
+            .filter(|method| method.pinvoke_library().is_none())
+            .map(|method| method.name().into())
+            .collect();
+        self.functions.retain(|method| {
+            method.pinvoke_library().is_none() || !implemented.contains(method.name())
+        });
+    }
+    /// Returns all calls to `<Module>` methods which are not defined within this assembly, as pairs of (caller, callee) names.
+    pub fn undefined_calls(&self) -> Vec<(&str, &str)> {
+        let defined: HashSet<&str> = self.functions.iter().map(Method::name).collect();
+        let mut undefined: Vec<(&str, &str)> = self
+            .functions
+            .iter()
+            .chain(self.types.iter().flat_map(TypeDef::methods))
+            .flat_map(|method| {
+                method.get_ops().iter().filter_map(move |op| match op {
+                    CILOp::Call(site)
+                    | CILOp::CallVirt(site)
+                    | CILOp::TailCall(site)
+                    | CILOp::LDFtn(site)
+                    | CILOp::NewObj(site)
+                        if site.class().is_none() =>
+                    {
+                        Some((method.name(), site.name()))
+                    }
+                    _ => None,
+                })
+            })
+            .filter(|(_, callee)| !defined.contains(callee))
+            .collect();
+        undefined.sort_unstable();
+        undefined.dedup();
+        undefined
+    }
+    /// Checks if `name` is the symbol of a function skipped because it has no MIR.
+    pub fn has_no_mir(&self, name: &str) -> bool {
+        self.no_mir.contains(name)
+    }
+    /// Returns names of all `<Module>` methods defined more than once, with different signatures. Definitions differing only in their
+    /// bodies(eg. the same inline function codegened in 2 crates) are not conflicts, since either one can be used.
+    pub fn conflicting_definitions(&self) -> Vec<&str> {
+        let mut sigs: HashMap<&str, &FnSig> = HashMap::new();
+        let mut conflicts: Vec<&str> = self
+            .functions
+            .iter()
+            .filter(|method| *sigs.entry(method.name()).or_insert(method.sig()) != method.sig())
+            .map(Method::name)
+            .collect();
+        conflicts.sort_unstable();
+        conflicts.dedup();
+        conflicts
+    }
     /// Adds a definition of a type to the assembly.
     pub fn add_typedef(&mut self, type_def: TypeDef) {
         self.types.insert(type_def);
     }
+    /// Removes the definition of type `name` from the assembly, and returns it, so that it can be modified and added back.
+    pub fn take_typedef(&mut self, name: &str) -> Option<TypeDef> {
+        let type_def = self.types.iter().find(|tpe| tpe.name() == name)?.clone();
+        self.types.take(&type_def)
+    }
     /// Adds a MIR item (method,inline assembly code, etc.) to the assembly.
     pub fn add_item<'tcx>(
         &mut self,
@@ -170,6 +319,71 @@ impl Assembly {
         self.entrypoint = Some(entrypoint);
     }
 }
+/// Creates an op marking the following ops as compiled from `span`. Spans coming from macro expansions are mapped back to the macro invocation within `mir`.
+fn source_info_op(tcx: TyCtxt, mir: &Body, span: Span) -> Option<CILOp> {
+    let span = rustc_span::hygiene::walk_chain(span, mir.span.ctxt());
+    let (file, line_start, col_start, line_end, col_end) =
+        tcx.sess.source_map().span_to_location_info(span);
+    Some(CILOp::SourceInfo {
+        file: file?.name.prefer_local().to_string().into(),
+        line_start: line_start as u32,
+        col_start: col_start as u32,
+        line_end: line_end as u32,
+        col_end: col_end as u32,
+    })
+}
+/// Gets the names of arguments and locals of `mir` from its debug info. Arguments are named after the parameters of the function, if possible.
+/// The locals are in the order used by [`locals_from_mir`].
+fn debug_names<'tyctx>(
+    mir: &Body<'tyctx>,
+    tyctx: TyCtxt<'tyctx>,
+    method_instance: &Instance<'tyctx>,
+    argc: usize,
+) -> (Vec<Option<IString>>, Vec<Option<IString>>) {
+    let mut arg_names = vec![None; argc];
+    let mut local_names = vec![None; mir.local_decls.len() - argc];
+    let mut used_names = HashSet::new();
+    for var in &mir.var_debug_info {
+        // Variables split into multiple locals, or stored in a field of a local, can't be named.
+        if var.composite.is_some() {
+            continue;
+        }
+        let VarDebugInfoContents::Place(place) = var.value else {
+            continue;
+        };
+        let local = place.local.as_usize();
+        if !place.projection.is_empty() || local == 0 {
+            continue;
+        }
+        let name: IString = var.name.as_str().into();
+        if local <= argc {
+            arg_names[local - 1].get_or_insert(name);
+        } else {
+            // Shadowed variables share a name, so the later ones get the index of their local appended.
+            let name = if used_names.insert(name.clone()) {
+                name
+            } else {
+                format!("{name}_{local}").into()
+            };
+            local_names[local - argc].get_or_insert(name);
+        }
+    }
+    let def_id = method_instance.def_id();
+    if matches!(method_instance.def, InstanceDef::Item(_))
+        && matches!(tyctx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+    {
+        let param_names = tyctx.fn_arg_names(def_id);
+        if param_names.len() == argc {
+            for (arg_name, param) in arg_names.iter_mut().zip(param_names) {
+                // Parameters which are patterns(like `(a, b): (u8, u8)`) have no name.
+                if param.name != kw::Empty && param.name != kw::Underscore {
+                    *arg_name = Some(param.name.as_str().into());
+                }
+            }
+        }
+    }
+    (arg_names, local_names)
+}
 /// Returns the list of all local variables within MIR of a function, and converts them to the internal type represenation `Type`
 fn locals_from_mir<'tyctx>(
     locals: &rustc_index::IndexVec<Local, LocalDecl<'tyctx>>,
@@ -191,4 +405,51 @@ fn locals_from_mir<'tyctx>(
         }
     }
     local_types
-}
\ No newline at end of file
+}
+#[test]
+fn undefined_and_conflicting() {
+    let sig = FnSig::new(&[], &Type::Void);
+    let mut caller = Method::new(AccessModifer::Public, true, sig.clone(), "caller", vec![]);
+    caller.set_ops(vec![
+        CILOp::Call(CallSite::boxed(None, "missing".into(), sig.clone(), true)),
+        CILOp::Call(CallSite::boxed(None, "dup".into(), sig.clone(), true)),
+    ]);
+    let mut asm = Assembly::empty();
+    asm.add_method(caller);
+    asm.add_method(Method::new(
+        AccessModifer::Public,
+        true,
+        sig.clone(),
+        "dup",
+        vec![],
+    ));
+    asm.add_method(Method::new(
+        AccessModifer::Public,
+        true,
+        FnSig::new(&[Type::I32], &Type::Void),
+        "dup",
+        vec![],
+    ));
+    let mut same_sig = Method::new(AccessModifer::Public, true, sig, "same_sig", vec![]);
+    asm.add_method(same_sig.clone());
+    same_sig.set_ops(vec![CILOp::Nop]);
+    asm.add_method(same_sig);
+    assert_eq!(asm.undefined_calls(), [("caller", "missing")]);
+    assert_eq!(asm.conflicting_definitions(), ["dup"]);
+}
+#[test]
+fn extended_type_not_duplicated() {
+    let mut extended = TypeDef::nameonly("Point");
+    extended.add_method(Method::new(
+        AccessModifer::Public,
+        false,
+        FnSig::new(&[Type::USize], &Type::I32),
+        "GetHashCode",
+        vec![],
+    ));
+    let mut asm = Assembly::empty();
+    asm.add_typedef(extended.clone());
+    asm.add_typedef_if_missing(TypeDef::nameonly("Point"));
+    assert_eq!(asm.types().count(), 1);
+    assert_eq!(asm.take_typedef("Point"), Some(extended));
+}
@@ -1,15 +1,25 @@
 use super::AssemblyExporter;
 use crate::{
     access_modifier::AccessModifer,
-    assembly_exporter::AssemblyExportError,
+    assembly_exporter::{AssemblyExportError, AssemblyInfo},
+    dotnet_metadata::AssemblyRef,
     method::Method,
-    r#type::{DotnetTypeRef, Type},
+    r#type::{DotnetArray, DotnetTypeRef, Type},
     type_def::TypeDef,
 };
 use std::{borrow::Cow, io::Write, ops::Deref};
 #[must_use]
 pub struct ILASMExporter {
     encoded_asm: Vec<u8>,
+    /// Set if any method contains source info, in which case a PDB is generated.
+    has_debug_info: bool,
+}
+impl ILASMExporter {
+    /// The CIL emitted so far.
+    #[cfg(test)]
+    pub(crate) fn cil(&self) -> &str {
+        std::str::from_utf8(&self.encoded_asm).expect("Emitted CIL is not UTF-8!")
+    }
 }
 impl std::io::Write for ILASMExporter {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
@@ -21,17 +31,27 @@ impl std::io::Write for ILASMExporter {
     }
 }
 impl AssemblyExporter for ILASMExporter {
-    fn init(asm_name: &str) -> Self {
+    fn init(asm_info: &AssemblyInfo) -> Self {
         let mut encoded_asm = Vec::with_capacity(0x1_00);
-        write!(encoded_asm, ".assembly {asm_name}{{}}").expect("Write error!");
-        Self { encoded_asm }
+        assembly_info_cil(&mut encoded_asm, asm_info).expect("Write error!");
+        Self {
+            encoded_asm,
+            has_debug_info: false,
+        }
     }
     fn add_type(&mut self, tpe: &TypeDef) {
         type_def_cli(&mut self.encoded_asm, tpe).expect("Error");
         //let _ = self.types.push(tpe.clone());
     }
     fn add_method(&mut self, method: &Method) {
-        method_cil(&mut self.encoded_asm, method).expect("Error");
+        self.has_debug_info |= method
+            .get_ops()
+            .iter()
+            .any(|op| matches!(op, crate::cil_op::CILOp::SourceInfo { .. }));
+        method_cil(&mut self.encoded_asm, method, false).expect("Error");
+    }
+    fn add_extern_ref(&mut self, asm_ref: &AssemblyRef) {
+        extern_ref_cil(&mut self.encoded_asm, asm_ref).expect("Write error!");
     }
     fn finalize(
         self,
@@ -64,11 +84,15 @@ impl AssemblyExporter for ILASMExporter {
             "-output:{out_path}",
             out_path = out_path.clone().to_string_lossy()
         );
-        let args: [String; 3] = [
+        let mut args: Vec<String> = vec![
             asm_type.into(),
             target,
             cil_path.clone().to_string_lossy().to_string(),
         ];
+        if self.has_debug_info {
+            // Emits a PDB with the `.line` sequence points, without disabling JIT optimizations.
+            args.push("-debug=opt".into());
+        }
         let out = std::process::Command::new("ilasm")
             .args(args)
             .output()
@@ -101,26 +125,48 @@ fn type_def_cli(w: &mut impl Write, tpe: &TypeDef) -> Result<(), super::Assembly
     if tpe.gargc() != 0 {
         generics.push('>');
     }
-    let extended = if let Some(_extended) = tpe.extends() {
-        todo!("Can't handle inheretence yet!");
+    let extended = if let Some(extended) = tpe.extends() {
+        dotnet_type_ref_cli(extended).into()
     } else {
-        "[System.Runtime]System.ValueType"
+        Cow::Borrowed("[System.Runtime]System.ValueType")
     };
     let access = if let AccessModifer::Public = tpe.access_modifier() {
         "public"
     } else {
         "private"
     };
-    writeln!(w, "\n.class {access} {name}{generics} extends {extended}{{")?;
+    let static_kw = if tpe.is_static() {
+        "abstract sealed "
+    } else {
+        ""
+    };
+    let mut implements = String::new();
+    for (index, interface) in tpe.implements().iter().enumerate() {
+        implements.push_str(if index == 0 { " implements " } else { ", " });
+        implements.push_str(&dotnet_type_ref_cli(interface));
+    }
+    writeln!(
+        w,
+        "\n.class {access} {static_kw}{name}{generics} extends {extended}{implements}{{"
+    )?;
     for inner_type in tpe.inner_types() {
         type_def_cli(w, inner_type)?;
     }
     let _field_string = String::new();
+    // Fields private in Rust are still accessed by functions outside of the type, so they are only hidden from other assemblies.
+    let field_access = |field_name: &str| {
+        if tpe.is_field_internal(field_name) {
+            "assembly"
+        } else {
+            "public"
+        }
+    };
     if let Some(offsets) = tpe.explicit_offsets() {
         for ((field_name, field_type), offset) in tpe.fields().iter().zip(offsets.iter()) {
             writeln!(
                 w,
-                "\t.field [{offset}] public {field_type_name} {field_name}",
+                "\t.field [{offset}] {access} {field_type_name} {field_name}",
+                access = field_access(field_name),
                 field_type_name = prefixed_type_cil(field_type)
             )?;
         }
@@ -128,17 +174,41 @@ fn type_def_cli(w: &mut impl Write, tpe: &TypeDef) -> Result<(), super::Assembly
         for (field_name, field_type) in tpe.fields() {
             writeln!(
                 w,
-                "\t.field public {field_type_name} {field_name}",
+                "\t.field {access} {field_type_name} {field_name}",
+                access = field_access(field_name),
                 field_type_name = prefixed_type_cil(field_type)
             )?;
         }
     }
-    for (_, method) in tpe.methods().enumerate() {
-        method_cil(w, method)?;
+    for method in tpe.methods() {
+        let is_accessor = tpe
+            .properties()
+            .iter()
+            .any(|(_, _, getter)| getter.as_ref() == method.name());
+        method_cil(w, method, is_accessor)?;
+    }
+    for (property_name, property_type, getter) in tpe.properties() {
+        property_cil(w, name, property_name, property_type, getter)?;
     }
     writeln!(w, "}}")?;
     Ok(())
 }
+/// Emits property `name` of type `owner`, whose value is returned by instance method `getter`.
+fn property_cil(
+    w: &mut impl Write,
+    owner: &str,
+    name: &str,
+    tpe: &Type,
+    getter: &str,
+) -> std::io::Result<()> {
+    let tpe = output_type_cil(tpe);
+    writeln!(
+        w,
+        "\t.property instance {tpe} {name}(){{\n\t\t.get instance {tpe} {owner}::{getter}()\n\t}}",
+        name = method_name_cil(name),
+        getter = method_name_cil(getter)
+    )
+}
 fn absolute_path(path: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
     if path.has_root() {
         Ok(path.to_owned())
@@ -148,11 +218,56 @@ fn absolute_path(path: &std::path::Path) -> std::io::Result<std::path::PathBuf>
         Ok(abs_path)
     }
 }
-fn method_cil(w: &mut impl Write, method: &Method) -> std::io::Result<()> {
-    let access = if let AccessModifer::Private = method.access() {
-        "private"
-    } else {
-        "public"
+fn assembly_info_cil(w: &mut impl Write, asm_info: &AssemblyInfo) -> std::io::Result<()> {
+    writeln!(w, ".assembly {name}{{", name = asm_info.name())?;
+    let [major, minor, build, revision] = asm_info.version();
+    writeln!(w, "\t.ver {major}:{minor}:{build}:{revision}")?;
+    // Custom attribute blob: prolog, the version as a length-prefixed string, and no named arguments.
+    let mut blob = vec![0x01, 0x00];
+    let informational_version = asm_info.informational_version().as_bytes();
+    blob.extend(compressed_len(informational_version.len()));
+    blob.extend(informational_version);
+    blob.extend([0x00, 0x00]);
+    let blob: Vec<_> = blob.iter().map(|byte| format!("{byte:02X}")).collect();
+    writeln!(
+        w,
+        "\t.custom instance void [System.Runtime]System.Reflection.AssemblyInformationalVersionAttribute::.ctor(string) = ({blob})",
+        blob = blob.join(" ")
+    )?;
+    write!(w, "}}")
+}
+/// Encodes `len` the way lengths of blobs and strings within custom attributes are encoded.
+fn compressed_len(len: usize) -> Vec<u8> {
+    match len {
+        0..=0x7F => vec![len as u8],
+        0x80..=0x3FFF => vec![0x80 | (len >> 8) as u8, len as u8],
+        _ => vec![
+            0xC0 | (len >> 24) as u8,
+            (len >> 16) as u8,
+            (len >> 8) as u8,
+            len as u8,
+        ],
+    }
+}
+fn extern_ref_cil(w: &mut impl Write, asm_ref: &AssemblyRef) -> std::io::Result<()> {
+    writeln!(w, "\n.assembly extern {name}{{", name = asm_ref.name())?;
+    let [major, minor, build, revision] = asm_ref.version();
+    writeln!(w, "\t.ver {major}:{minor}:{build}:{revision}")?;
+    if let Some(token) = asm_ref.public_key_token() {
+        let token: Vec<_> = token.iter().map(|byte| format!("{byte:02X}")).collect();
+        writeln!(w, "\t.publickeytoken = ({token})", token = token.join(" "))?;
+    }
+    if let Some(culture) = asm_ref.culture() {
+        writeln!(w, "\t.culture \"{culture}\"")?;
+    }
+    write!(w, "}}")
+}
+/// Emits `method`. Property accessors(`is_accessor`) are marked `specialname`.
+fn method_cil(w: &mut impl Write, method: &Method, is_accessor: bool) -> std::io::Result<()> {
+    let access = match method.access() {
+        AccessModifer::Private => "private",
+        AccessModifer::Public => "public",
+        AccessModifer::Family => "family",
     };
     let static_inst = if method.is_static() {
         "static"
@@ -161,11 +276,30 @@ fn method_cil(w: &mut impl Write, method: &Method) -> std::io::Result<()> {
     };
     let output = output_type_cil(method.sig().output());
     let name = method.name();
+    if let Some(library) = method.pinvoke_library() {
+        write!(
+            w,
+            ".method {access} hidebysig {static_inst} pinvokeimpl(\"{library}\" cdecl) {output}{marshal} {name}",
+            marshal = marshal_cil(method.sig().output()),
+            name = method_name_cil(name)
+        )?;
+        pinvoke_args_cli(w, method.explicit_inputs())?;
+        return writeln!(w, " cil managed preservesig{{}}");
+    }
+    let virtual_kw = if method.is_virtual() { "virtual " } else { "" };
+    let special = if name == ".ctor" {
+        "specialname rtspecialname "
+    } else if is_accessor {
+        "specialname "
+    } else {
+        ""
+    };
     write!(
         w,
-        ".method {access} hidebysig {static_inst} {output} {name}"
+        ".method {access} hidebysig {special}{virtual_kw}{static_inst} {output} {name}",
+        name = method_name_cil(name)
     )?;
-    args_cli(w, method.explicit_inputs())?;
+    args_cli(w, method)?;
     writeln!(w, "{{")?;
     if method.is_entrypoint() {
         writeln!(w, ".entrypoint")?;
@@ -175,15 +309,17 @@ fn method_cil(w: &mut impl Write, method: &Method) -> std::io::Result<()> {
     if let Some((local_id, local)) = locals_iter.next() {
         write!(
             w,
-            "\t\t[{local_id}] {escaped_type}",
-            escaped_type = arg_type_cil(local)
+            "\t\t[{local_id}] {escaped_type}{name}",
+            escaped_type = arg_type_cil(local),
+            name = name_cil(method.local_name(local_id))
         )?;
     }
     for (local_id, local) in locals_iter {
         write!(
             w,
-            ",\n\t\t[{local_id}] {escaped_type}",
-            escaped_type = arg_type_cil(local)
+            ",\n\t\t[{local_id}] {escaped_type}{name}",
+            escaped_type = arg_type_cil(local),
+            name = name_cil(method.local_name(local_id))
         )?;
     }
     writeln!(w, "\n\t)")?;
@@ -201,11 +337,20 @@ fn op_cli(op: &crate::cil_op::CILOp) -> Cow<'static, str> {
         CILOp::Ret => "ret".into(),
         CILOp::Label(id) => format!("bb_{id}:").into(),
         CILOp::GoTo(id) => format!("br bb_{id}").into(),
+        CILOp::Leave(id) => format!("leave bb_{id}").into(),
+        CILOp::BeginTry => ".try {".into(),
+        CILOp::EndTry | CILOp::EndCatch => "}".into(),
+        CILOp::BeginCatch(class) => format!("catch {} {{", dotnet_type_ref_cli(class)).into(),
         CILOp::BEq(id) => format!("beq bb_{id}").into(),
         CILOp::BNe(id) => format!("bne.un bb_{id}").into(),
         CILOp::BGe(id) => format!("bge bb_{id}").into(),
         CILOp::BLt(id) => format!("blt bb_{id}").into(),
+        CILOp::BLtUn(id) => format!("blt.un bb_{id}").into(),
         CILOp::BZero(id) => format!("brzero bb_{id}").into(),
+        CILOp::Switch(targets) => {
+            let targets: Vec<_> = targets.iter().map(|id| format!("bb_{id}")).collect();
+            format!("switch ({targets})", targets = targets.join(",")).into()
+        }
         CILOp::Call(call_site) => {
             if call_site.is_nop() {
                 "".into()
@@ -234,12 +379,23 @@ fn op_cli(op: &crate::cil_op::CILOp) -> Cow<'static, str> {
                 //println!("inputs:{inputs:?} input_string: {input_string}",inputs = call_site.signature.inputs);
                 format!(
                     "call {prefix} {output} {owner_name} {function_name}({input_string})",
-                    function_name = call_site.name(),
+                    function_name = method_name_cil(call_site.name()),
                     output = call_output_type_cil(call_site.signature().output())
                 )
                 .into()
             }
         }
+        CILOp::TailCall(call_site) => format!(
+            "tail. {call}",
+            call = op_cli(&CILOp::Call(call_site.clone()))
+        )
+        .into(),
+        // `ldftn` refers to a method the same way `call` does.
+        CILOp::LDFtn(call_site) => format!(
+            "ldftn {site}",
+            site = op_cli(&CILOp::Call(call_site.clone())).trim_start_matches("call")
+        )
+        .into(),
         CILOp::CallVirt(call_site) => {
             if call_site.is_nop() {
                 "".into()
@@ -268,7 +424,7 @@ fn op_cli(op: &crate::cil_op::CILOp) -> Cow<'static, str> {
                 //println!("inputs:{inputs:?} input_string: {input_string}",inputs = call_site.signature.inputs);
                 format!(
                     "callvirt {prefix} {output} {owner_name} {function_name}({input_string})",
-                    function_name = call_site.name(),
+                    function_name = method_name_cil(call_site.name()),
                     output = call_output_type_cil(call_site.signature().output())
                 )
                 .into()
@@ -378,6 +534,17 @@ fn op_cli(op: &crate::cil_op::CILOp) -> Cow<'static, str> {
         CILOp::LdcF64(f64const) => format!("ldc.r8 {f64const}").into(),
         //Debug
         CILOp::Comment(comment) => format!("//{comment}").into(),
+        CILOp::SourceInfo {
+            file,
+            line_start,
+            col_start,
+            line_end,
+            col_end,
+        } => format!(
+            ".line {line_start},{line_end}:{col_start},{col_end} '{file}'",
+            file = file.replace('\\', "\\\\").replace('\'', "\\'")
+        )
+        .into(),
         //Convertions
         CILOp::ConvISize(checked) => {
             if *checked {
@@ -479,6 +646,7 @@ fn op_cli(op: &crate::cil_op::CILOp) -> Cow<'static, str> {
         CILOp::STIndF64 => "stind.r8".into(),
         CILOp::LDIndISize => "ldind.i".into(),
         CILOp::STIndISize => "stind.i".into(),
+        CILOp::STIndRef => "stind.ref".into(),
         CILOp::LocAlloc => "localloc".into(),
         //OOP
         CILOp::SizeOf(tpe) => format!("sizeof {tpe}", tpe = prefixed_type_cil(tpe)).into(),
@@ -490,6 +658,18 @@ fn op_cli(op: &crate::cil_op::CILOp) -> Cow<'static, str> {
             tpe = prefixed_field_type_cil(&obj.as_ref().clone().into())
         )
         .into(),
+        CILOp::Box(tpe) => format!("box {}", prefixed_field_type_cil(tpe)).into(),
+        CILOp::UnboxAny(tpe) => format!("unbox.any {}", prefixed_field_type_cil(tpe)).into(),
+        CILOp::CallI(sig) => {
+            let inputs: Vec<_> = sig.inputs().iter().map(call_arg_type_cil).collect();
+            format!(
+                "calli {output}({inputs})",
+                output = call_output_type_cil(sig.output()),
+                inputs = inputs.join(",")
+            )
+            .into()
+        }
+        CILOp::CastClass(tpe) => format!("castclass {}", prefixed_field_type_cil(tpe)).into(),
         CILOp::STObj(obj) => format!(
             "stobj {tpe}",
             tpe = prefixed_field_type_cil(&obj.as_ref().clone().into())
@@ -542,7 +722,7 @@ fn op_cli(op: &crate::cil_op::CILOp) -> Cow<'static, str> {
                 //println!("inputs:{inputs:?} input_string: {input_string}",inputs = call_site.signature.inputs);
                 format!(
                     "newobj {prefix} {output} {owner_name}{function_name}({input_string})",
-                    function_name = call_site.name(),
+                    function_name = method_name_cil(call_site.name()),
                     output = output_type_cil(call_site.signature().output())
                 )
                 .into()
@@ -555,10 +735,90 @@ fn op_cli(op: &crate::cil_op::CILOp) -> Cow<'static, str> {
         CILOp::Pop => "pop".into(),
         CILOp::Dup => "dup".into(),
         CILOp::LDStaticField(static_field) => {
-            todo!("Can't load static field {static_field:?}");
+            format!("ldsfld {}", static_field_cil(static_field)).into()
+        }
+        CILOp::STStaticField(static_field) => {
+            format!("stsfld {}", static_field_cil(static_field)).into()
+        }
+        CILOp::NewArr(array) => {
+            if array.dimensions == 1 {
+                format!("newarr {}", prefixed_type_cil(&array.element)).into()
+            } else {
+                format!(
+                    "newobj instance void {}::.ctor({})",
+                    array_cil(array),
+                    array_indices_cil(array)
+                )
+                .into()
+            }
+        }
+        CILOp::LDLen(array) => {
+            if array.dimensions == 1 {
+                "ldlen".into()
+            } else {
+                "callvirt instance int32 [System.Runtime]System.Array::get_Length()".into()
+            }
+        }
+        CILOp::LDElem(array) => {
+            let element = prefixed_type_cil(&array.element);
+            if array.dimensions == 1 {
+                format!("ldelem {element}").into()
+            } else {
+                format!(
+                    "call instance {element} {}::Get({})",
+                    array_cil(array),
+                    array_indices_cil(array)
+                )
+                .into()
+            }
+        }
+        CILOp::STElem(array) => {
+            let element = prefixed_type_cil(&array.element);
+            if array.dimensions == 1 {
+                format!("stelem {element}").into()
+            } else {
+                format!(
+                    "call instance void {}::Set({},{element})",
+                    array_cil(array),
+                    array_indices_cil(array)
+                )
+                .into()
+            }
+        }
+        CILOp::LDElemAdress(array) => {
+            let element = prefixed_type_cil(&array.element);
+            if array.dimensions == 1 {
+                format!("ldelema {element}").into()
+            } else {
+                format!(
+                    "call instance {element}& {}::Address({})",
+                    array_cil(array),
+                    array_indices_cil(array)
+                )
+                .into()
+            }
         } //_ => todo!("Unsuported op {op:?}"),
     }
 }
+fn array_cil(array: &DotnetArray) -> Cow<'static, str> {
+    prefixed_type_cil(&Type::DotnetArray(Box::new(array.clone())))
+}
+/// Parameters of the methods accessing elements of a multi-dimensional array: one `int32` index per dimension.
+fn array_indices_cil(array: &DotnetArray) -> String {
+    vec!["int32"; array.dimensions as usize].join(",")
+}
+/// Reference to a static field. Fields with no owner belong to the `<Module>` class.
+fn static_field_cil(descr: &crate::cil_op::StaticFieldDescriptor) -> String {
+    let owner = match descr.owner() {
+        Some(owner) => format!("{}::", prefixed_field_type_cil(&owner.clone().into())),
+        None => String::new(),
+    };
+    format!(
+        "{prefixed_type} {owner}{field_name}",
+        prefixed_type = prefixed_field_type_cil(descr.tpe()),
+        field_name = descr.name()
+    )
+}
 fn output_type_cil(tpe: &Type) -> Cow<'static, str> {
     match tpe {
         Type::Void => "void".into(),
@@ -615,6 +875,7 @@ fn type_cil(tpe: &Type) -> Cow<'static, str> {
         Type::U128 => "[System.Rutnime]System.UInt128".into(),
         Type::ISize => "native int".into(),
         Type::USize => "native uint".into(),
+        Type::Ptr(inner) if **inner == Type::Void => "void*".into(),
         Type::Ptr(inner) => format!("{inner}*", inner = type_cil(inner)).into(),
         Type::DotnetType(dotnet_type) => dotnet_type_ref_cli(dotnet_type).into(),
         //Special type
@@ -629,13 +890,15 @@ fn type_cil(tpe: &Type) -> Cow<'static, str> {
             } else {
                 "".into()
             };
-            format!("{tpe}[{arr}]", tpe = type_cil(&array.element)).into()
+            format!("{tpe}[{arr}]", tpe = prefixed_type_cil(&array.element)).into()
         } //_ => todo!("Unsuported type {tpe:?}"),
         Type::FnDef(site) => "FnDef".into(),
+        Type::PinnedRef(inner) => format!("{inner}& pinned", inner = type_cil(inner)).into(),
     }
 }
 fn field_type_cil(tpe: &Type) -> Cow<'static, str> {
     match tpe {
+        Type::Ptr(inner) if **inner == Type::Void => "void*".into(),
         Type::Ptr(inner) => format!("{inner}*", inner = type_cil(inner)).into(),
         Type::GenericArg(id) => format!("!{id}").into(),
         Type::DotnetType(dotnet_type) => dotnet_type_ref_cli_generics_unescaped(dotnet_type).into(),
@@ -644,6 +907,7 @@ fn field_type_cil(tpe: &Type) -> Cow<'static, str> {
 }
 fn prefixed_field_type_cil(tpe: &Type) -> Cow<'static, str> {
     match tpe {
+        Type::Ptr(inner) if **inner == Type::Void => "void*".into(),
         Type::Ptr(inner) => format!("{inner}*", inner = prefixed_field_type_cil(inner)).into(),
         Type::GenericArg(id) => format!("!{id}").into(),
         Type::DotnetType(dotnet_type) => dotnet_type_ref_cli_generics_unescaped(dotnet_type).into(),
@@ -667,12 +931,16 @@ fn prefixed_type_cil(tpe: &Type) -> Cow<'static, str> {
         Type::U128 => "valuetype [System.Runtime]System.UInt128".into(),
         Type::ISize => "native int".into(),
         Type::USize => "native uint".into(),
+        Type::Ptr(inner) if **inner == Type::Void => "void*".into(),
         Type::Ptr(inner) => format!("{inner}*", inner = prefixed_type_cil(inner)).into(),
         Type::DotnetType(dotnet_type) => {
             let prefix = dotnet_type.tpe_prefix();
             format!("{prefix} {}", dotnet_type_ref_cli(dotnet_type)).into()
         }
         Type::FnDef(site) => "valuetype FnDef".into(),
+        Type::PinnedRef(inner) => {
+            format!("{inner}& pinned", inner = prefixed_type_cil(inner)).into()
+        }
         //Special type
         Type::Unresolved => "valuetype Unresolved".into(),
         Type::Foreign => "valuetype Foreign".into(),
@@ -685,24 +953,71 @@ fn prefixed_type_cil(tpe: &Type) -> Cow<'static, str> {
             } else {
                 "".into()
             };
-            format!("{tpe}[{arr}]", tpe = type_cil(&array.element)).into()
+            format!("{tpe}[{arr}]", tpe = prefixed_type_cil(&array.element)).into()
         } //_ => todo!("Unsuported type {tpe:?}"),
     };
     println!("prefixed_type:{prefixed_type}, type:{tpe:?}");
     prefixed_type
 }
-fn args_cli(w: &mut impl Write, args: &[Type]) -> std::io::Result<()> {
-    let mut args = args.iter();
+fn args_cli(w: &mut impl Write, method: &Method) -> std::io::Result<()> {
+    // Argument names of instance methods include `this`, which is not in the argument list.
+    let first_explicit = usize::from(!method.is_static());
+    let mut args = method.explicit_inputs().iter().enumerate();
     write!(w, "(")?;
-    if let Some(first_arg) = args.next() {
-        write!(w, "{type_cil}", type_cil = arg_type_cil(first_arg))?;
+    if let Some((arg_id, first_arg)) = args.next() {
+        write!(
+            w,
+            "{type_cil}{name}",
+            type_cil = arg_type_cil(first_arg),
+            name = name_cil(method.arg_name(arg_id + first_explicit))
+        )?;
     }
-    for arg in args {
-        write!(w, ",{type_cil}", type_cil = arg_type_cil(arg))?;
+    for (arg_id, arg) in args {
+        write!(
+            w,
+            ",{type_cil}{name}",
+            type_cil = arg_type_cil(arg),
+            name = name_cil(method.arg_name(arg_id + first_explicit))
+        )?;
     }
     write!(w, ")")?;
     Ok(())
 }
+/// Quotes the name of a method, so that names which are also CIL keywords(eg. `add`) can be used. Special names(`.ctor`) are left as-is.
+fn method_name_cil(name: &str) -> Cow<str> {
+    if name.starts_with('.') {
+        name.into()
+    } else {
+        format!("'{name}'").into()
+    }
+}
+/// Name of an argument or local, quoted so that it can't clash with ilasm keywords(like `value`).
+fn name_cil(name: Option<&str>) -> String {
+    name.map(|name| format!(" '{name}'")).unwrap_or_default()
+}
+fn pinvoke_args_cli(w: &mut impl Write, args: &[Type]) -> std::io::Result<()> {
+    let args: Vec<_> = args
+        .iter()
+        .map(|arg| {
+            format!(
+                "{type_cil}{marshal}",
+                type_cil = arg_type_cil(arg),
+                marshal = marshal_cil(arg)
+            )
+        })
+        .collect();
+    write!(w, "({args})", args = args.join(","))
+}
+/// Native representation of a type passed to or returned from a P/Invoke function, if it differs from the default one.
+fn marshal_cil(tpe: &Type) -> &'static str {
+    match tpe {
+        // Rust bools are 1 byte wide, while the default marshalling assumes a 4 byte wide win32 `BOOL`.
+        Type::Bool => " marshal(unsigned int8)",
+        // Chars would be converted to ANSI by default.
+        Type::DotnetChar => " marshal(unsigned int16)",
+        _ => "",
+    }
+}
 fn call_args_cli(w: &mut impl Write, args: &[Type]) -> std::io::Result<()> {
     let mut args = args.iter();
     write!(w, "(")?;
@@ -772,3 +1087,122 @@ fn tuple_type() {
         &prefixed_field_type_cil(&generic)
     );
 }
+#[test]
+fn switch_op() {
+    use crate::cil_op::CILOp;
+    assert_eq!(
+        "switch (bb_3,bb_1,bb_3)",
+        &op_cli(&CILOp::Switch(vec![3, 1, 3]))
+    );
+}
+#[test]
+fn tail_call_op() {
+    use crate::cil_op::{CILOp, CallSite};
+    let site = CallSite::boxed(
+        None,
+        "fib".into(),
+        crate::function_sig::FnSig::new(&[Type::U64], &Type::U64),
+        true,
+    );
+    let tail_call = op_cli(&CILOp::TailCall(site.clone()));
+    assert_eq!(
+        tail_call,
+        format!("tail. {call}", call = op_cli(&CILOp::Call(site)))
+    );
+}
+#[test]
+fn pinvoke_method() {
+    use crate::method::Attribute;
+    let mut method = Method::new(
+        AccessModifer::Private,
+        true,
+        crate::function_sig::FnSig::new(&[Type::I32, Type::Bool], &Type::Bool),
+        "is_odd",
+        vec![],
+    );
+    method.add_attribute(Attribute::PInvoke {
+        library: "native".into(),
+    });
+    method.ensure_valid();
+    let mut out = Vec::new();
+    method_cil(&mut out, &method, false).unwrap();
+    assert_eq!(
+        ".method private hidebysig static pinvokeimpl(\"native\" cdecl) bool marshal(unsigned int8) 'is_odd'(int32,bool marshal(unsigned int8)) cil managed preservesig{}\n",
+        String::from_utf8(out).unwrap()
+    );
+}
+#[test]
+fn extern_ref() {
+    let mut out = Vec::new();
+    extern_ref_cil(&mut out, &AssemblyRef::new("Lib")).unwrap();
+    assert_eq!(
+        "\n.assembly extern Lib{\n\t.ver 0:0:0:0\n}",
+        String::from_utf8(out).unwrap()
+    );
+}
+#[test]
+fn assembly_info() {
+    let mut out = Vec::new();
+    assembly_info_cil(&mut out, &AssemblyInfo::new("app", "1.2.0-rc").unwrap()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with(".assembly app{\n\t.ver 1:2:0:0\n"));
+    assert!(out.contains("::.ctor(string) = (01 00 08 31 2E 32 2E 30 2D 72 63 00 00)"));
+}
+#[test]
+fn internal_field_and_property() {
+    let mut tpe = TypeDef::nameonly("Counter");
+    tpe.add_field("count".into(), Type::U32);
+    tpe.add_internal_field("step".into(), Type::U32);
+    tpe.add_method(Method::new(
+        AccessModifer::Public,
+        false,
+        crate::function_sig::FnSig::new(&[Type::U32], &Type::U32),
+        "count",
+        vec![],
+    ));
+    tpe.add_property("Count".into(), Type::U32, "count".into());
+    let mut out = Vec::new();
+    type_def_cli(&mut out, &tpe).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains(".field public uint32 count"));
+    assert!(out.contains(".field assembly uint32 step"));
+    assert!(out.contains(".method public hidebysig specialname instance uint32 'count'()"));
+    assert!(out.contains(".property instance uint32 'Count'()"));
+    assert!(out.contains(".get instance uint32 Counter::'count'()"));
+}
+#[test]
+fn source_info_op() {
+    use crate::cil_op::CILOp;
+    let op = CILOp::SourceInfo {
+        file: "src/main.rs".into(),
+        line_start: 3,
+        col_start: 5,
+        line_end: 4,
+        col_end: 12,
+    };
+    assert_eq!(op_cli(&op), ".line 3,4:5,12 'src/main.rs'");
+}
+#[test]
+fn named_locals_and_args() {
+    let mut method = Method::new(
+        AccessModifer::Public,
+        true,
+        crate::function_sig::FnSig::new(&[Type::I32, Type::U8], &Type::Void),
+        "named",
+        vec![Type::I32, Type::F32],
+    );
+    method.set_arg_names(vec![Some("count".into()), None]);
+    method.set_local_names(vec![None, Some("value".into())]);
+    let mut out = Vec::new();
+    method_cil(&mut out, &method, false).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("'named'(int32 'count',uint8)"));
+    assert!(out.contains("[0] int32,"));
+    assert!(out.contains("[1] float32 'value'"));
+}
+#[test]
+fn void_ptr() {
+    let ptr = Type::Ptr(Type::Void.into());
+    assert_eq!(type_cil(&ptr), "void*");
+    assert_eq!(prefixed_type_cil(&ptr), "void*");
+}
@@ -1,24 +1,94 @@
+use serde::{Deserialize, Serialize};
 use std::path::Path;
-type AssemblyInfo = str;
+/// Name and version of an assembly.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AssemblyInfo {
+    name: IString,
+    version: [u16; 4],
+    /// Version string in its original form(eg. `1.0.0-beta.2`), which may not be representable as an assembly version.
+    informational_version: IString,
+}
+impl AssemblyInfo {
+    /// Creates assembly info with name `name` and version parsed from `version`. Returns [`None`] if `version` is not a valid version.
+    pub fn new(name: &str, version: &str) -> Option<Self> {
+        Some(Self {
+            name: name.into(),
+            version: parse_version(version)?,
+            informational_version: version.into(),
+        })
+    }
+    /// Creates assembly info for crate `crate_name`. The version is read from `CARGO_PKG_VERSION`, which is set by cargo.
+    pub fn from_crate(crate_name: &str) -> Self {
+        std::env::var("CARGO_PKG_VERSION")
+            .ok()
+            .and_then(|version| Self::new(crate_name, &version))
+            .unwrap_or_else(|| Self::new(crate_name, "0.0.0").unwrap())
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.into();
+    }
+    /// Version of the assembly, in the major, minor, build, revision order.
+    pub fn version(&self) -> [u16; 4] {
+        self.version
+    }
+    /// Sets the version of this assembly to `version`. Returns [`None`] if `version` is not a valid version.
+    pub fn set_version(&mut self, version: &str) -> Option<()> {
+        self.version = parse_version(version)?;
+        self.informational_version = version.into();
+        Some(())
+    }
+    pub fn informational_version(&self) -> &str {
+        &self.informational_version
+    }
+}
+impl Default for AssemblyInfo {
+    fn default() -> Self {
+        Self::new("asm", "0.0.0").unwrap()
+    }
+}
+/// Parses a semver-style version(`1.2.3-pre+build`) into an assembly version. Pre-release and build metadata is not a part of the assembly version.
+fn parse_version(version: &str) -> Option<[u16; 4]> {
+    let numeric = version.split(['-', '+']).next()?;
+    let mut parsed = [0; 4];
+    let mut parts = numeric.split('.');
+    for (part, parsed) in parts.by_ref().zip(parsed.iter_mut()) {
+        *parsed = part.parse().ok()?;
+    }
+    // More than 4 parts can't be represented.
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(parsed)
+}
 #[derive(Debug, Clone)]
 enum AccessModifer {
     Private,
     Public,
 }
-use crate::{assembly::Assembly, method::Method, type_def::TypeDef, IString};
+use crate::{
+    assembly::Assembly, dotnet_metadata::AssemblyRef, method::Method, type_def::TypeDef, IString,
+};
 pub mod ilasm_exporter;
 pub trait AssemblyExporter: Sized {
     fn init(asm_info: &AssemblyInfo) -> Self;
     fn add_type(&mut self, tpe: &TypeDef);
     fn add_method(&mut self, method: &Method);
-    //fn extern_asm(&mut self,asm:&str);
+    /// Adds a reference to external assembly `asm_ref`.
+    fn add_extern_ref(&mut self, asm_ref: &AssemblyRef);
     fn finalize(self, final_path: &Path, is_dll: bool) -> Result<(), AssemblyExportError>;
     fn export_assembly(
         asm: &Assembly,
         final_path: &Path,
         is_dll: bool,
+        extern_refs: &[AssemblyRef],
     ) -> Result<(), AssemblyExportError> {
-        let mut asm_exporter = Self::init("asm");
+        let mut asm_exporter = Self::init(&asm.info().cloned().unwrap_or_default());
+        for extern_ref in extern_refs {
+            asm_exporter.add_extern_ref(extern_ref);
+        }
         for tpe in asm.types() {
             asm_exporter.add_type(tpe);
         }
@@ -48,3 +118,10 @@ impl From<std::io::Error> for AssemblyExportError {
         Self::IoError(error)
     }
 }
+#[test]
+fn version_parsing() {
+    assert_eq!(parse_version("1.2.3"), Some([1, 2, 3, 0]));
+    assert_eq!(parse_version("0.1.0-beta.2+build.5"), Some([0, 1, 0, 0]));
+    assert_eq!(parse_version("1.2.3.4.5"), None);
+    assert_eq!(parse_version("one"), None);
+}
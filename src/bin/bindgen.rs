@@ -0,0 +1,40 @@
+use rustc_codegen_clr::{bindgen, dotnet_metadata::Metadata};
+use std::env;
+/// Prints mycorrhiza bindings to the listed types, defined in a .NET assembly.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        eprintln!("Usage: {} ASSEMBLY.dll TYPE...", args[0]);
+        std::process::exit(1);
+    }
+    let assembly_path = &args[1];
+    let pe = std::fs::read(assembly_path).unwrap_or_else(|err| {
+        eprintln!("error: could not read {assembly_path:?}: {err}");
+        std::process::exit(1);
+    });
+    let metadata = Metadata::from_pe(&pe).unwrap_or_else(|err| {
+        eprintln!("error: could not read the metadata of {assembly_path:?}: {err:?}");
+        std::process::exit(1);
+    });
+    let assembly = metadata.assembly_ref().unwrap_or_else(|err| {
+        eprintln!("error: could not read the identity of {assembly_path:?}: {err:?}");
+        std::process::exit(1);
+    });
+    let type_defs = metadata.type_defs().unwrap_or_else(|err| {
+        eprintln!("error: could not read the types defined in {assembly_path:?}: {err:?}");
+        std::process::exit(1);
+    });
+    let types: Vec<_> = args[2..]
+        .iter()
+        .map(|path| {
+            type_defs
+                .iter()
+                .find(|type_def| type_def.name.path.as_ref() == path && type_def.is_public())
+                .unwrap_or_else(|| {
+                    eprintln!("error: {assembly_path:?} defines no public type {path}");
+                    std::process::exit(1);
+                })
+        })
+        .collect();
+    print!("{}", bindgen::bindings(assembly.name(), &types));
+}
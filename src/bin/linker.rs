@@ -1,8 +1,25 @@
 #![deny(unused_must_use)]
 //use assembly::Assembly;
 use rustc_codegen_clr::*;
+use std::collections::HashMap;
 use std::env;
-fn load_ar(r: &mut impl std::io::Read) -> std::io::Result<assembly::Assembly> {
+use std::path::{Path, PathBuf};
+/// Maps names of methods to the object files/archives they were defined in.
+type SymbolSources = HashMap<String, Vec<String>>;
+/// Records that all methods in `assembly` were defined in `source`.
+fn record_sources(assembly: &assembly::Assembly, source: &str, sources: &mut SymbolSources) {
+    for method in assembly.methods() {
+        sources
+            .entry(method.name().into())
+            .or_default()
+            .push(source.into());
+    }
+}
+fn load_ar(
+    r: &mut impl std::io::Read,
+    ar_path: &str,
+    sources: &mut SymbolSources,
+) -> std::io::Result<assembly::Assembly> {
     use ar::Archive;
     use std::io::Read;
     let mut final_assembly = assembly::Assembly::empty();
@@ -18,6 +35,7 @@ fn load_ar(r: &mut impl std::io::Read) -> std::io::Result<assembly::Assembly> {
                 .expect("ERROR: Could not load the assembly file!");
             let assembly = postcard::from_bytes(&asm_bytes)
                 .expect("ERROR:Could not decode the assembly file!");
+            record_sources(&assembly, &format!("{ar_path}({name})"), sources);
             final_assembly = final_assembly.join(assembly);
         }
     }
@@ -50,37 +68,317 @@ impl AOTCompileMode {
             }
         }
     }
+    fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "no" | "none" | "no_aot" | "no-aot" => Ok(Self::NoAOT),
+            "mono" | "mono_aot" | "mono-aot" => Ok(Self::MonoAOT),
+            "mono_full" | "mono-full" | "mono_full_aot" | "mono-full-aot" => Ok(Self::FullMonoAOT),
+            _ => Err(format!("Unknown AOT mode:{name:?}")),
+        }
+    }
+}
+/// Kind of the assembly produced by the linker.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum OutputKind {
+    Exe,
+    Dll,
+}
+impl OutputKind {
+    fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "exe" | "bin" => Ok(Self::Exe),
+            "dll" | "lib" => Ok(Self::Dll),
+            _ => Err(format!(
+                "Unknown output kind:{name:?}. Expected \"exe\" or \"dll\"."
+            )),
+        }
+    }
+}
+/// Runtime the produced assembly is meant to run under.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum TargetRuntime {
+    CoreCLR,
+    Mono,
+}
+impl TargetRuntime {
+    fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "coreclr" | "dotnet" => Ok(Self::CoreCLR),
+            "mono" => Ok(Self::Mono),
+            _ => Err(format!(
+                "Unknown target runtime:{name:?}. Expected \"coreclr\" or \"mono\"."
+            )),
+        }
+    }
 }
-fn aot_compile_mode(args: &[String]) -> AOTCompileMode {
-    if let Some(aot_idx) = args.iter().position(|arg| arg == "--aot_mode") {
-        let aot_idx = aot_idx + 1;
-        let aot = args
-            .get(aot_idx)
-            .expect("ERROR: \"--aot_mode\" provided, but no AOT mode set!");
-        match aot.as_str() {
-            "no" | "none" | "no_aot" | "no-aot" => AOTCompileMode::NoAOT,
-            "mono" | "mono_aot" | "mono-aot" => AOTCompileMode::MonoAOT,
-            "mono_full" | "mono-full" | "mono_full_aot" | "mono-full-aot" => {
-                AOTCompileMode::FullMonoAOT
+/// Flags rustc passes to gcc-flavored linkers, which only make sense when linking native code.
+const IGNORED_NATIVE_FLAGS: &[&str] = &[
+    "-m64",
+    "-m32",
+    "-pie",
+    "-no-pie",
+    "-static",
+    "-static-pie",
+    "-nostartfiles",
+    "-nostdlib",
+    "-rdynamic",
+    "-fPIC",
+];
+/// Command-line arguments of the linker. The linker accepts the same flags rustc passes to gcc-flavored linkers, as well as some .NET specific options.
+struct LinkerArgs {
+    /// Path of the final assembly.
+    output: String,
+    /// Serialized assemblies(`.bc` files) to link.
+    assemblies: Vec<String>,
+    /// Archives(`.rlib` files) containing serialized assemblies to link.
+    archives: Vec<String>,
+    /// Managed `.dll` files the final assembly references.
+    references: Vec<String>,
+    /// Directories searched for libraries passed with `-l`.
+    search_paths: Vec<PathBuf>,
+    /// Names of libraries passed with `-l`, resolved after all search paths are known.
+    libs: Vec<String>,
+    /// `-shared` was passed.
+    shared: bool,
+    output_kind: Option<OutputKind>,
+    /// Overrides the name of the final assembly, which is by default the name of the crate being linked.
+    asm_name: Option<String>,
+    /// Overrides the version of the final assembly, which is by default the version of the crate being linked.
+    asm_version: Option<String>,
+    runtime: Option<TargetRuntime>,
+    aot: AOTCompileMode,
+    /// Target framework moniker(eg. `net7.0`) written to `.runtimeconfig.json`.
+    tfm: String,
+    roll_forward: runtime_config::RollForward,
+    /// Should unreachable methods and types be removed. Can be turned off with `-Wl,--no-gc-sections`.
+    gc_sections: bool,
+    /// Should a C# facade(`.cs` file with wrappers around exported methods) be written next to a library.
+    cs_facade: bool,
+}
+impl LinkerArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self {
+            output: String::new(),
+            assemblies: vec![],
+            archives: vec![],
+            references: vec![],
+            search_paths: vec![],
+            libs: vec![],
+            shared: false,
+            output_kind: None,
+            asm_name: None,
+            asm_version: None,
+            runtime: None,
+            aot: AOTCompileMode::NoAOT,
+            tfm: runtime_config::DEFAULT_TFM.into(),
+            roll_forward: runtime_config::RollForward::LatestMajor,
+            gc_sections: true,
+            cs_facade: false,
+        };
+        let mut output = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                // Everything past `--` is an input file.
+                for input in args.by_ref() {
+                    parsed.add_input(input)?;
+                }
+                break;
+            }
+            if let Some(value) = short_flag_value("-o", &arg, &mut args)? {
+                output = Some(value);
+            } else if let Some(value) = short_flag_value("-L", &arg, &mut args)? {
+                parsed.search_paths.push(value.into());
+            } else if let Some(value) = short_flag_value("-l", &arg, &mut args)? {
+                parsed.libs.push(value);
+            } else if let Some(native_args) = arg.strip_prefix("-Wl,") {
+                for native_arg in native_args.split(',') {
+                    parsed.native_linker_arg(native_arg);
+                }
+            } else if arg == "-Xlinker" {
+                let native_arg = args.next().ok_or_else(|| {
+                    "\"-Xlinker\" provided, but no argument follows it!".to_owned()
+                })?;
+                parsed.native_linker_arg(&native_arg);
+            } else if arg == "-shared" {
+                parsed.shared = true;
+            } else if arg == "--cs-facade" {
+                parsed.cs_facade = true;
+            } else if arg == "-nodefaultlibs" {
+                // The libc shims are not a native default library, but a part of the runtime support code, so they are always inserted.
+            } else if let Some(value) = long_flag_value("--output-kind", &arg, &mut args)? {
+                parsed.output_kind = Some(OutputKind::from_name(&value)?);
+            } else if let Some(value) = long_flag_value("--assembly-name", &arg, &mut args)? {
+                parsed.asm_name = Some(value);
+            } else if let Some(value) = long_flag_value("--assembly-version", &arg, &mut args)? {
+                parsed.asm_version = Some(value);
+            } else if let Some(value) = long_flag_value("--runtime", &arg, &mut args)? {
+                parsed.runtime = Some(TargetRuntime::from_name(&value)?);
+            } else if let Some(value) = long_flag_value("--reference", &arg, &mut args)? {
+                parsed.references.push(value);
+            } else if let Some(value) = long_flag_value("--tfm", &arg, &mut args)? {
+                parsed.tfm = value;
+            } else if let Some(value) = long_flag_value("--roll-forward", &arg, &mut args)? {
+                parsed.roll_forward = runtime_config::RollForward::from_name(&value)
+                    .ok_or_else(|| format!("Unknown roll forward policy:{value:?}"))?;
+            } else if let Some(value) = long_flag_value("--aot-mode", &arg, &mut args)? {
+                parsed.aot = AOTCompileMode::from_name(&value)?;
+            } else if let Some(value) = long_flag_value("--aot_mode", &arg, &mut args)? {
+                parsed.aot = AOTCompileMode::from_name(&value)?;
+            } else if IGNORED_NATIVE_FLAGS.contains(&arg.as_str())
+                || arg.starts_with("-fuse-ld=")
+                || arg.starts_with("-B")
+            {
+                // Native-only flag, nothing to do.
+            } else if arg.starts_with('-') {
+                return Err(format!("Unknown flag:{arg:?}"));
+            } else {
+                parsed.add_input(arg)?;
             }
-            _ => panic!("Unknown AOT mode:{aot:?}"),
         }
-    } else {
-        AOTCompileMode::NoAOT
+        parsed.output = output.ok_or_else(|| "No output file!".to_owned())?;
+        for lib in std::mem::take(&mut parsed.libs) {
+            parsed.resolve_lib(&lib);
+        }
+        if !matches!(parsed.aot, AOTCompileMode::NoAOT)
+            && parsed.runtime == Some(TargetRuntime::CoreCLR)
+        {
+            return Err("AOT compilation is only supported when targeting mono!".into());
+        }
+        Ok(parsed)
+    }
+    /// Handles an input file, based on its extension.
+    fn add_input(&mut self, input: String) -> Result<(), String> {
+        match Path::new(&input).extension().and_then(|ext| ext.to_str()) {
+            Some("bc") => self.assemblies.push(input),
+            Some("rlib") => self.archives.push(input),
+            Some("dll") => self.references.push(input),
+            // Native object files and libraries(like the `symbols.o` rustc generates) can't be a part of a .NET assembly.
+            Some("o" | "a" | "so") => (),
+            _ => return Err(format!("Unknown input file kind:{input:?}")),
+        }
+        Ok(())
+    }
+    /// Handles an argument meant for a native linker(passed via `-Wl,` or `-Xlinker`). Most of them have no meaning for .NET assemblies, and are ignored.
+    fn native_linker_arg(&mut self, arg: &str) {
+        match arg {
+            "--gc-sections" => self.gc_sections = true,
+            "--no-gc-sections" => self.gc_sections = false,
+            _ => (),
+        }
+    }
+    /// Resolves library `lib` passed with `-l`. If a managed `{lib}.dll` is present in one of the search paths, it becomes a reference.
+    /// Othewise, the library is native, and its functions are resolved at runtime.
+    fn resolve_lib(&mut self, lib: &str) {
+        let managed = self
+            .search_paths
+            .iter()
+            .map(|dir| dir.join(format!("{lib}.dll")))
+            .find(|path| path.exists());
+        if let Some(managed) = managed {
+            self.references.push(managed.to_string_lossy().into_owned());
+        }
+    }
+    fn output_kind(&self) -> OutputKind {
+        self.output_kind.unwrap_or_else(|| {
+            let is_lib_ext = matches!(
+                Path::new(&self.output)
+                    .extension()
+                    .and_then(|ext| ext.to_str()),
+                Some("dll" | "so")
+            );
+            if self.shared || is_lib_ext {
+                OutputKind::Dll
+            } else {
+                OutputKind::Exe
+            }
+        })
+    }
+    fn runtime(&self) -> TargetRuntime {
+        self.runtime.unwrap_or(match self.aot {
+            AOTCompileMode::NoAOT => TargetRuntime::CoreCLR,
+            _ => TargetRuntime::Mono,
+        })
+    }
+}
+/// Gets the value of short flag `flag`, passed either as `-fvalue` or `-f value`.
+fn short_flag_value(
+    flag: &str,
+    arg: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<Option<String>, String> {
+    match arg.strip_prefix(flag) {
+        None => Ok(None),
+        Some("") => args
+            .next()
+            .map(Some)
+            .ok_or_else(|| format!("{flag:?} provided, but no value follows it!")),
+        Some(value) => Ok(Some(value.to_owned())),
+    }
+}
+/// Gets the value of long flag `flag`, passed either as `--flag=value`, `--flag,value`(like `-C link-arg` does) or `--flag value`.
+fn long_flag_value(
+    flag: &str,
+    arg: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<Option<String>, String> {
+    match arg.strip_prefix(flag) {
+        None => Ok(None),
+        Some("") => args
+            .next()
+            .map(Some)
+            .ok_or_else(|| format!("{flag:?} provided, but no value follows it!")),
+        Some(value) => match value.strip_prefix(['=', ',']) {
+            Some(value) => Ok(Some(value.to_owned())),
+            // Just a flag with a common prefix.
+            None => Ok(None),
+        },
     }
 }
+/// Reports calls to undefined methods and methods defined with conflicting signatures, like a native linker would. Returns `false` if any errors were found.
+fn check_symbols(final_assembly: &assembly::Assembly, sources: &SymbolSources) -> bool {
+    let source_of = |name: &str| {
+        sources
+            .get(name)
+            .map(|sources| sources.join(", "))
+            // Methods without a source were inserted by the linker itself(eg. libc functions).
+            .unwrap_or_else(|| "<linker>".into())
+    };
+    // Functions without MIR are skipped by the codegen. Calls to them only fail when executed, so they are not errors.
+    let (no_mir, undefined): (Vec<_>, Vec<_>) = final_assembly
+        .undefined_calls()
+        .into_iter()
+        .partition(|(_, callee)| final_assembly.has_no_mir(callee));
+    for (caller, callee) in &no_mir {
+        eprintln!(
+            "warning: symbol {callee} has no MIR, calling it will throw MissingMethodException\n>>> referenced by {caller} in {source}",
+            source = source_of(caller)
+        );
+    }
+    for (caller, callee) in &undefined {
+        eprintln!(
+            "error: undefined symbol: {callee}\n>>> referenced by {caller} in {source}",
+            source = source_of(caller)
+        );
+    }
+    let conflicting = final_assembly.conflicting_definitions();
+    for name in &conflicting {
+        eprintln!(
+            "error: symbol {name} defined multiple times with different signatures\n>>> defined in {sources}",
+            sources = source_of(name)
+        );
+    }
+    undefined.is_empty() && conflicting.is_empty()
+}
 fn main() {
     use std::io::Read;
-    let args: Vec<String> = env::args().collect();
-    let args = &args[1..];
-    let to_link: Vec<_> = args.iter().filter(|arg| arg.contains(".bc")).collect();
-    let ar_to_link: Vec<_> = args.iter().filter(|arg| arg.contains(".rlib")).collect();
-    let output = &args[1 + args
-        .iter()
-        .position(|arg| arg == "-o")
-        .expect("No output file!")];
+    let args = LinkerArgs::parse(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    });
     let mut final_assembly = assembly::Assembly::empty();
-    for asm_path in &to_link {
+    let mut sources = SymbolSources::new();
+    for asm_path in &args.assemblies {
         let mut asm_file =
             std::fs::File::open(asm_path).expect("ERROR:Could not open the assembly file!");
         let mut asm_bytes = Vec::with_capacity(0x100);
@@ -89,22 +387,82 @@ fn main() {
             .expect("ERROR: Could not load the assembly file!");
         let assembly =
             postcard::from_bytes(&asm_bytes).expect("ERROR:Could not decode the assembly file!");
+        record_sources(&assembly, asm_path, &mut sources);
         final_assembly = final_assembly.join(assembly);
     }
-    for asm_path in &ar_to_link {
+    for asm_path in &args.archives {
         let mut asm_file =
             std::fs::File::open(asm_path).expect("ERROR: Could not open the assembly file!");
-        let assembly = load_ar(&mut asm_file).expect("Could not oper archive");
+        let assembly =
+            load_ar(&mut asm_file, asm_path, &mut sources).expect("Could not oper archive");
         final_assembly = final_assembly.join(assembly);
     }
     libc::insert_libc(&mut final_assembly);
     use rustc_codegen_clr::assembly_exporter::AssemblyExporter;
-    let path = output;
-    let is_lib = output.contains(".dll") || output.contains(".so") || output.contains(".o");
+    let is_lib = args.output_kind() == OutputKind::Dll;
+    if args.gc_sections {
+        // Public items of libraries may be used by other assemblies, so they must be kept.
+        final_assembly.eliminate_dead_code(is_lib);
+    }
+    if !check_symbols(&final_assembly, &sources) {
+        std::process::exit(1);
+    }
+    if args.asm_name.is_some() || args.asm_version.is_some() {
+        let mut info = final_assembly.info().cloned().unwrap_or_default();
+        if let Some(name) = &args.asm_name {
+            info.set_name(name);
+        }
+        if let Some(version) = &args.asm_version {
+            if info.set_version(version).is_none() {
+                eprintln!("error: {version:?} is not a valid assembly version!");
+                std::process::exit(1);
+            }
+        }
+        final_assembly.set_info(info);
+    }
+    let extern_refs: Vec<_> = args
+        .references
+        .iter()
+        .map(|reference| {
+            dotnet_metadata::AssemblyRef::from_dll(Path::new(reference)).unwrap_or_else(|err| {
+                eprintln!("error: could not read the identity of assembly {reference:?}: {err:?}");
+                std::process::exit(1);
+            })
+        })
+        .collect();
     rustc_codegen_clr::assembly_exporter::ilasm_exporter::ILASMExporter::export_assembly(
         &final_assembly,
-        path.as_ref(),
+        args.output.as_ref(),
         is_lib,
+        &extern_refs,
     )
     .expect("Assembly export faliure!");
+    if is_lib && args.cs_facade {
+        std::fs::write(
+            Path::new(&args.output).with_extension("cs"),
+            csharp_facade::csharp_facade(&final_assembly),
+        )
+        .expect("Could not write the C# facade!");
+    }
+    // CoreCLR needs to know which runtime version to use. The config is not needed only if the executable is explicitly meant for mono.
+    if !is_lib && args.runtime != Some(TargetRuntime::Mono) {
+        let config = runtime_config::RuntimeConfig::new(&args.tfm, args.roll_forward)
+            .unwrap_or_else(|| {
+                eprintln!(
+                    "error: {tfm:?} is not a .NET Core target framework!",
+                    tfm = args.tfm
+                );
+                std::process::exit(1);
+            });
+        config
+            .write(
+                Path::new(&args.output),
+                &final_assembly.info().cloned().unwrap_or_default(),
+                &extern_refs,
+            )
+            .expect("Could not write the runtime config!");
+    }
+    if args.runtime() == TargetRuntime::Mono {
+        args.aot.compile(&args.output);
+    }
 }
@@ -0,0 +1,360 @@
+use crate::dotnet_metadata::{MethodDefInfo, SigType, TypeDefInfo};
+use std::collections::HashSet;
+use std::fmt::Write;
+/// Highest number of arguments mycorrhiza helpers can pass to a managed method.
+const MAX_ARGS: usize = 16;
+/// Generates the source of a mycorrhiza module, with a `RustcCLRInteropManagedClass` alias and wrapper methods for each of `types`,
+/// defined in assembly `assembly`. Value types, generic types and methods with arguments not representable in Rust are skipped.
+pub fn bindings(assembly: &str, types: &[&TypeDefInfo]) -> String {
+    let mut out = format!("//! Bindings to types from `{assembly}`, generated by `bindgen`.\n");
+    for tpe in types {
+        type_bindings(&mut out, assembly, tpe);
+    }
+    out
+}
+fn type_bindings(out: &mut String, assembly: &str, tpe: &TypeDefInfo) {
+    let path = tpe.name.path.as_ref();
+    if tpe.is_value_type() || path.contains(['`', '/']) {
+        return;
+    }
+    let alias = path.rsplit('.').next().unwrap_or(path);
+    writeln!(
+        out,
+        "pub type {alias} =\n    crate::intrinsics::RustcCLRInteropManagedClass<\"{assembly}\", \"{path}\">;"
+    )
+    .unwrap();
+    let abstract_class = tpe.flags & 0x80 != 0;
+    let wrappers: Vec<Wrapper> = tpe
+        .methods
+        .iter()
+        .filter(|method| method.is_public() && !method.is_generic)
+        .filter(|method| !(abstract_class && method.name.as_ref() == ".ctor"))
+        .filter_map(|method| Wrapper::new(assembly, tpe, method))
+        .collect();
+    writeln!(out, "impl {alias} {{").unwrap();
+    for (wrapper, name) in wrappers.iter().zip(overload_names(&wrappers)) {
+        wrapper.write(out, &name);
+    }
+    writeln!(out, "}}").unwrap();
+}
+/// What a wrapper does.
+enum WrapperKind {
+    Ctor,
+    Getter(String),
+    Setter(String),
+    Method,
+}
+/// A Rust method wrapping a managed method.
+struct Wrapper<'a> {
+    method: &'a MethodDefInfo,
+    kind: WrapperKind,
+    /// Name of the wrapper, before overloads are disambiguated.
+    base_name: String,
+    /// Names and Rust types of the arguments.
+    args: Vec<(String, &'a SigType, String)>,
+    output: String,
+}
+impl<'a> Wrapper<'a> {
+    /// Returns [`None`] if `method` can't be called from Rust.
+    fn new(assembly: &str, owner: &TypeDefInfo, method: &'a MethodDefInfo) -> Option<Self> {
+        if method.params.len() > MAX_ARGS {
+            return None;
+        }
+        let args = method
+            .params
+            .iter()
+            .map(|(name, tpe)| {
+                Some((
+                    escape_ident(&snake_case(name)),
+                    tpe,
+                    rust_type(assembly, owner, tpe)?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let output = rust_type(assembly, owner, &method.output)?;
+        let name = method.name.as_ref();
+        let special = |prefix: &str| {
+            name.strip_prefix(prefix)
+                .filter(|_| method.is_special_name())
+                .map(String::from)
+        };
+        let (kind, base_name) = if name == ".ctor" && !method.is_static() {
+            (WrapperKind::Ctor, "new".into())
+        } else if name == ".cctor" {
+            return None;
+        } else if let Some(property) = special("get_").filter(|_| args.is_empty()) {
+            (WrapperKind::Getter(property.clone()), snake_case(&property))
+        } else if let Some(property) = special("set_").filter(|_| args.len() == 1) {
+            let base_name = format!("set_{}", snake_case(&property));
+            (WrapperKind::Setter(property), base_name)
+        } else {
+            (WrapperKind::Method, snake_case(name))
+        };
+        Some(Self {
+            method,
+            kind,
+            base_name,
+            args,
+            output,
+        })
+    }
+    fn write(&self, out: &mut String, name: &str) {
+        let receiver = if self.method.is_static() || matches!(self.kind, WrapperKind::Ctor) {
+            ""
+        } else if self.args.is_empty() {
+            "self"
+        } else {
+            "self, "
+        };
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|(name, _, tpe)| format!("{name}: {tpe}"))
+            .collect();
+        let output = match self.kind {
+            WrapperKind::Ctor => " -> Self".into(),
+            _ if self.output == "()" => String::new(),
+            _ => format!(" -> {}", self.output),
+        };
+        writeln!(
+            out,
+            "    #[inline(always)]\n    pub fn {name}({receiver}{args}){output} {{",
+            args = args.join(", ")
+        )
+        .unwrap();
+        let argc = self.args.len();
+        let arg_names: Vec<&str> = self.args.iter().map(|(name, _, _)| name.as_str()).collect();
+        let arg_names = arg_names.join(", ");
+        let mut generics: Vec<String> = self.args.iter().map(|(_, _, tpe)| tpe.clone()).collect();
+        let method_name = self.method.name.as_ref();
+        let target = if self.method.is_static() {
+            "Self::"
+        } else {
+            "self."
+        };
+        let body = match &self.kind {
+            WrapperKind::Ctor if argc == 0 => "Self::ctor0()".into(),
+            WrapperKind::Ctor => format!(
+                "Self::ctor{argc}::<{generics}>({arg_names})",
+                generics = generics.join(", ")
+            ),
+            WrapperKind::Getter(property) if self.method.is_static() => format!(
+                "Self::get_static_property::<\"{property}\", {}>()",
+                self.output
+            ),
+            WrapperKind::Getter(property) => {
+                format!("self.get_property::<\"{property}\", {}>()", self.output)
+            }
+            WrapperKind::Setter(property) if self.method.is_static() => format!(
+                "Self::set_static_property::<\"{property}\", {}>({arg_names})",
+                generics[0]
+            ),
+            WrapperKind::Setter(property) => format!(
+                "self.set_property::<\"{property}\", {}>({arg_names})",
+                generics[0]
+            ),
+            WrapperKind::Method => {
+                let helper = if self.method.is_static() {
+                    "static"
+                } else if self.method.is_virtual() {
+                    "virt"
+                } else {
+                    "instance"
+                };
+                generics.insert(0, format!("\"{method_name}\""));
+                generics.push(self.output.clone());
+                format!(
+                    "{target}{helper}{argc}::<{generics}>({arg_names})",
+                    generics = generics.join(", ")
+                )
+            }
+        };
+        writeln!(out, "        {body}\n    }}").unwrap();
+    }
+}
+/// Returns the final names of `wrappers`. Overloaded methods get their argument types appended to their names, and any remaining
+/// conflicts are resolved by appending a number.
+fn overload_names(wrappers: &[Wrapper]) -> Vec<String> {
+    let mut used = HashSet::new();
+    wrappers
+        .iter()
+        .map(|wrapper| {
+            let overloaded = wrappers
+                .iter()
+                .filter(|other| other.base_name == wrapper.base_name)
+                .count()
+                > 1;
+            let mut name = wrapper.base_name.clone();
+            if overloaded {
+                for (_, tpe, _) in &wrapper.args {
+                    name.push('_');
+                    name.push_str(&type_suffix(tpe));
+                }
+            }
+            let mut unique = name.clone();
+            let mut index = 2;
+            while !used.insert(unique.clone()) {
+                unique = format!("{name}_{index}");
+                index += 1;
+            }
+            escape_ident(&unique)
+        })
+        .collect()
+}
+/// Returns the Rust equivalent of `tpe`, or [`None`] if it can't be used from Rust.
+fn rust_type(assembly: &str, owner: &TypeDefInfo, tpe: &SigType) -> Option<String> {
+    Some(match tpe {
+        SigType::Void => "()".into(),
+        SigType::Bool => "bool".into(),
+        SigType::Char => "crate::DotNetChar".into(),
+        SigType::I8 => "i8".into(),
+        SigType::U8 => "u8".into(),
+        SigType::I16 => "i16".into(),
+        SigType::U16 => "u16".into(),
+        SigType::I32 => "i32".into(),
+        SigType::U32 => "u32".into(),
+        SigType::I64 => "i64".into(),
+        SigType::U64 => "u64".into(),
+        SigType::F32 => "f32".into(),
+        SigType::F64 => "f64".into(),
+        SigType::ISize => "isize".into(),
+        SigType::USize => "usize".into(),
+        SigType::String => "crate::system::MString".into(),
+        SigType::Object => {
+            "crate::intrinsics::RustcCLRInteropManagedClass<\"System.Runtime\", \"System.Object\">"
+                .into()
+        }
+        SigType::Ptr(inner) => format!("*mut {}", rust_type(assembly, owner, inner)?),
+        SigType::SzArray(element) if **element != SigType::Void => format!(
+            "crate::intrinsics::RustcCLRInteropManagedArray<{}, 1>",
+            rust_type(assembly, owner, element)?
+        ),
+        SigType::Class(name) if name.path.contains(['`', '/']) => return None,
+        SigType::Class(name) if *name == owner.name => "Self".into(),
+        SigType::Class(name) => format!(
+            "crate::intrinsics::RustcCLRInteropManagedClass<\"{}\", \"{}\">",
+            name.assembly.as_deref().unwrap_or(assembly),
+            name.path
+        ),
+        SigType::SzArray(_) | SigType::ValueType(_) | SigType::Unsupported => return None,
+    })
+}
+/// Short name of `tpe`, used to tell overloads apart.
+fn type_suffix(tpe: &SigType) -> String {
+    match tpe {
+        SigType::Char => "char".into(),
+        SigType::String => "string".into(),
+        SigType::Object => "object".into(),
+        SigType::Ptr(inner) => format!("{}_ptr", type_suffix(inner)),
+        SigType::SzArray(element) => format!("{}_array", type_suffix(element)),
+        SigType::Class(name) | SigType::ValueType(name) => {
+            snake_case(name.path.rsplit('.').next().unwrap_or(&name.path))
+        }
+        // Primitive types are named like their Rust equivalents.
+        _ => format!("{tpe:?}").to_lowercase(),
+    }
+}
+/// Converts a PascalCase or camelCase name into snake_case. Acronyms are treated as one word(`IOStream` becomes `io_stream`).
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (index, chr) in chars.iter().enumerate() {
+        if chr.is_uppercase() && index > 0 {
+            let prev = chars[index - 1];
+            let next_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(chr.to_lowercase());
+    }
+    out
+}
+/// Makes `ident` usable as a Rust identifier, if it is a keyword.
+fn escape_ident(ident: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern",
+        "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use",
+        "where", "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv",
+        "try", "typeof", "unsized", "virtual", "yield",
+    ];
+    match ident {
+        // Those can't be raw identifiers.
+        "self" | "super" | "crate" => format!("{ident}_"),
+        _ if KEYWORDS.contains(&ident) => format!("r#{ident}"),
+        _ => ident.into(),
+    }
+}
+#[test]
+fn snake_case_names() {
+    assert_eq!(snake_case("ElapsedMilliseconds"), "elapsed_milliseconds");
+    assert_eq!(snake_case("IOStream"), "io_stream");
+    assert_eq!(snake_case("ToUInt64"), "to_u_int64");
+    assert_eq!(snake_case("value"), "value");
+}
+#[test]
+fn stopwatch_bindings() {
+    use crate::dotnet_metadata::ManagedTypeName;
+    let method =
+        |name: &str, flags: u16, params: Vec<(&str, SigType)>, output: SigType| MethodDefInfo {
+            name: name.into(),
+            flags,
+            params: params
+                .into_iter()
+                .map(|(name, tpe)| (name.into(), tpe))
+                .collect(),
+            output,
+            is_generic: false,
+        };
+    // Public, public static, public special name
+    let (public, public_static, special) = (0x6, 0x16, 0x806);
+    let stopwatch = TypeDefInfo {
+        name: ManagedTypeName {
+            assembly: None,
+            path: "System.Diagnostics.Stopwatch".into(),
+        },
+        extends: Some(ManagedTypeName {
+            assembly: None,
+            path: "System.Object".into(),
+        }),
+        flags: 0x1,
+        methods: vec![
+            method(".ctor", special, vec![], SigType::Void),
+            method("Start", public, vec![], SigType::Void),
+            method("get_ElapsedMilliseconds", special, vec![], SigType::I64),
+            method(
+                "Log",
+                public_static,
+                vec![("value", SigType::I64)],
+                SigType::Void,
+            ),
+            method(
+                "Log",
+                public_static,
+                vec![("text", SigType::String)],
+                SigType::Void,
+            ),
+            method(
+                "Log",
+                public_static,
+                vec![("span", SigType::Unsupported)],
+                SigType::Void,
+            ),
+        ],
+    };
+    let out = bindings("System.Runtime", &[&stopwatch]);
+    assert!(out.contains(
+        "RustcCLRInteropManagedClass<\"System.Runtime\", \"System.Diagnostics.Stopwatch\">;"
+    ));
+    assert!(out.contains("pub fn new() -> Self {\n        Self::ctor0()"));
+    assert!(out.contains("pub fn start(self) {\n        self.instance0::<\"Start\", ()>()"));
+    assert!(out.contains(
+        "pub fn elapsed_milliseconds(self) -> i64 {\n        self.get_property::<\"ElapsedMilliseconds\", i64>()"
+    ));
+    assert!(out.contains(
+        "pub fn log_i64(value: i64) {\n        Self::static1::<\"Log\", i64, ()>(value)"
+    ));
+    assert!(out.contains("pub fn log_string(text: crate::system::MString) {"));
+    assert!(!out.contains("span"));
+}
@@ -1,4 +1,8 @@
-use crate::{function_sig::FnSig, r#type::DotnetTypeRef, IString};
+use crate::{
+    function_sig::FnSig,
+    r#type::{DotnetArray, DotnetTypeRef},
+    IString,
+};
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
 /// This struct descibes a .NET field. It contains information about the type this field belongs to, the name of the field, and the fields type.
 pub struct FieldDescriptor {
@@ -160,12 +164,30 @@ pub enum CILOp {
     BLt(u32),
     /// Jump to target if the top value is greater than the bottom one, continue otherwise. WARING: make sure the compared values have the same type, othewise IL is invalid.
     BGe(u32),
+    /// Jump to target if the bottom value is less than the top one, comparing them as unsigned intiegers. WARING: make sure the compared values have the same type, othewise IL is invalid.
+    BLtUn(u32),
     /// Jump to target if the top value on the stack is zero, continue otherwise. WARING: make sure the compared values have the same type, othewise IL is invalid.
     BZero(u32),
+    /// Pops an unsigned 32 bit intieger `n` and jumps to the `n`-th target. If `n` is greater than or equal to the number of targets, execution continues.
+    Switch(Vec<u32>),
     /// Call the metod behind `call_site`.`
     Call(Box<CallSite>),
     /// Call the virtual method behind `call_site`.`
     CallVirt(Box<CallSite>),
+    /// Call the metod behind `call_site`, discarding the frame of the caller first. Must be immediately followed by [`CILOp::Ret`].
+    TailCall(Box<CallSite>),
+    /// Push a pointer to the native code of the method behind `call_site`.
+    LDFtn(Box<CallSite>),
+    /// Leave a protected region or a handler, emptying the stack and jumping to the label with the specified id.
+    Leave(u32),
+    /// Marks the beginning of a protected region. The stack must be empty.
+    BeginTry,
+    /// Marks the end of a protected region. Must be immediately followed by a handler.
+    EndTry,
+    /// Marks the beginning of a handler catching exceptions of type `class`. The caught exception is pushed on the stack.
+    BeginCatch(Box<DotnetTypeRef>),
+    /// Marks the end of a catch handler.
+    EndCatch,
     /// Throw the top value on the stack as an exception
     Throw,
     /// Rethrow the current exception
@@ -265,8 +287,17 @@ pub enum CILOp {
     STIndISize,
     STIndF32,
     STIndF64,
+    STIndRef,
     //Debugging
     Comment(IString),
+    /// Marks the ops following it as compiled from lines `line_start..=line_end` of Rust source file `file`. Columns are 1-based.
+    SourceInfo {
+        file: IString,
+        line_start: u32,
+        col_start: u32,
+        line_end: u32,
+        col_end: u32,
+    },
     // Arthmetic Operations
     Add,
     AddOvf,
@@ -300,19 +331,42 @@ pub enum CILOp {
     LDFieldAdress(Box<FieldDescriptor>),
     STField(Box<FieldDescriptor>),
     LdObj(Box<crate::r#type::Type>),
+    /// Pops a value of type `tpe`, and pushes a new object containing a copy of it.
+    Box(Box<crate::r#type::Type>),
+    /// Pops an object containing a value of type `tpe`(created by [`CILOp::Box`]), and pushes a copy of that value.
+    UnboxAny(Box<crate::r#type::Type>),
+    /// Pops a function pointer, and calls it with the arguments below it. The function must have signature `sig`.
+    CallI(Box<FnSig>),
+    /// Pops an object reference, and pushes it back if it refers to an instance of `tpe`. Throws `InvalidCastException` otherwise.
+    CastClass(Box<crate::r#type::Type>),
     STObj(Box<crate::r#type::Type>),
     SizeOf(Box<crate::r#type::Type>),
     LDStaticField(Box<StaticFieldDescriptor>),
+    STStaticField(Box<StaticFieldDescriptor>),
+    // Managed arrays. Multi-dimensional arrays are accesed using methods of the array type, so all those ops take an index for each dimension.
+    /// Pops the length of each dimension of `array`, and pushes a new array with those dimensions.
+    NewArr(Box<DotnetArray>),
+    /// Pops an array, and pushes the total number of its elements.
+    LDLen(Box<DotnetArray>),
+    /// Pops an array and an index for each of its dimensions, and pushes the element at that position.
+    LDElem(Box<DotnetArray>),
+    /// Pops an array, an index for each of its dimensions and a value, and sets the element at that position to the value.
+    STElem(Box<DotnetArray>),
+    /// Pops an array and an index for each of its dimensions, and pushes a managed reference to the element at that position.
+    LDElemAdress(Box<DotnetArray>),
 }
 impl CILOp {
     /// Descirbes the difference in stack size before and after the op.
     pub fn stack_diff(&self) -> isize {
         match self {
             CILOp::Nop => 0,
-            CILOp::Comment(_) => 0,
+            CILOp::Comment(_) | CILOp::SourceInfo { .. } => 0,
             CILOp::Label(_) | CILOp::GoTo(_) => 0,
+            CILOp::Leave(_) | CILOp::BeginTry | CILOp::EndTry | CILOp::EndCatch => 0,
+            CILOp::BeginCatch(_) => 1,
             CILOp::BZero(_) => -1,
-            CILOp::BEq(_) | CILOp::BNe(_) | CILOp::BLt(_) | CILOp::BGe(_) => -1,
+            CILOp::BEq(_) | CILOp::BNe(_) | CILOp::BLt(_) | CILOp::BGe(_) | CILOp::BLtUn(_) => -1,
+            CILOp::Switch(_) => -1,
             CILOp::LDArg(_) | CILOp::LDArgA(_) | CILOp::LDLoc(_) | CILOp::LDLocA(_) => 1,
             CILOp::LdcI32(_)
             | CILOp::LdcI64(_)
@@ -347,16 +401,24 @@ impl CILOp {
             | CILOp::STIndI64
             | CILOp::STIndISize
             | CILOp::STIndF32
-            | CILOp::STIndF64 => -2,
+            | CILOp::STIndF64
+            | CILOp::STIndRef => -2,
             CILOp::Pop => -1,
             CILOp::Dup => 1,
             CILOp::LDField(_) | CILOp::LDFieldAdress(_) => 0,
             CILOp::LocAlloc => 0,
             CILOp::NewObj(_) => 1,
             CILOp::LdObj(_) => 0,
+            CILOp::Box(_) | CILOp::UnboxAny(_) | CILOp::CastClass(_) => 0,
+            CILOp::LDFtn(_) => 1,
             CILOp::LDStaticField(_) => 1,
+            CILOp::STStaticField(_) => -1,
             CILOp::STObj(_) => -2,
             CILOp::STField(_) => -2,
+            CILOp::NewArr(array) => 1 - array.dimensions as isize,
+            CILOp::LDLen(_) => 0,
+            CILOp::LDElem(array) | CILOp::LDElemAdress(array) => -(array.dimensions as isize),
+            CILOp::STElem(array) => -(array.dimensions as isize) - 2,
             CILOp::Add
             | CILOp::AddOvf
             | CILOp::AddOvfUn
@@ -377,13 +439,20 @@ impl CILOp {
             | CILOp::Gt => -1,
             CILOp::Not | CILOp::Neg => 0,
             CILOp::STLoc(_) | CILOp::STArg(_) => -1,
-            CILOp::Call(site) | CILOp::CallVirt(site) => {
+            CILOp::Call(site) | CILOp::CallVirt(site) | CILOp::TailCall(site) => {
                 if *site.signature().output() == crate::r#type::Type::Void {
                     -(site.signature().inputs().len() as isize)
                 } else {
                     1 - (site.signature().inputs().len() as isize)
                 }
             }
+            CILOp::CallI(sig) => {
+                if *sig.output() == crate::r#type::Type::Void {
+                    -(sig.inputs().len() as isize) - 1
+                } else {
+                    -(sig.inputs().len() as isize)
+                }
+            }
             CILOp::Throw => -1,
             CILOp::Rethrow => -1,
             CILOp::Ret => -1,
@@ -1,8 +1,6 @@
 use std::path::Path;
 use std::path::PathBuf;
 fn test_dotnet_executable(file_path: &str, test_dir: &str) {
-    use std::io::Write;
-
     let exec_path = &format!("{file_path}.exe");
     if *IS_MONO_PRESENT {
         // Execute the test assembly
@@ -19,12 +17,7 @@ fn test_dotnet_executable(file_path: &str, test_dir: &str) {
     }
     //println!("exec_path:{exec_path:?}");
     if *IS_DOTNET_PRESENT {
-        let config_path = format!("{test_dir}/{file_path}.runtimeconfig.json");
-        println!("{config_path:?}");
-        let mut file = std::fs::File::create(config_path).unwrap();
-        file.write_all(RUNTIME_CONFIG.as_bytes())
-            .expect("COuld not write runtime config");
-        //RUNTIME_CONFIG
+        // The linker emits the runtime config next to the executable.
         let out = std::process::Command::new("dotnet")
             .current_dir(test_dir)
             .args([exec_path])
@@ -247,6 +240,54 @@ macro_rules! run_test {
         }
     };
 }
+/// Builds `./test/$prefix/$test_name.rs` as a `cdylib`, which makes the codegen export its public items, and checks that it links.
+macro_rules! test_cdylib {
+    ($prefix:ident,$test_name:ident) => {
+        mod $test_name {
+            fn build(opt_args: &[&str], output: &str) {
+                let test_dir = concat!("./test/", stringify!($prefix), "/");
+                // Ensures the test directory is present
+                std::fs::create_dir_all(test_dir).expect("Could not setup the test env");
+                // Builds the backend if neceasry
+                super::RUSTC_BUILD_STATUS
+                    .as_ref()
+                    .expect("Could not build rustc!");
+                // Compiles the test library
+                let out = std::process::Command::new("rustc")
+                    .current_dir(test_dir)
+                    .args(opt_args)
+                    .args([
+                        "--crate-type=cdylib",
+                        "-Z",
+                        super::backend_path(),
+                        "-C",
+                        &format!("linker={}", super::RUSTC_CODEGEN_CLR_LINKER.display()),
+                        concat!("./", stringify!($test_name), ".rs"),
+                        "-o",
+                        output,
+                    ])
+                    .output()
+                    .expect("failed to execute process");
+                // If stderr is not empty, then something went wrong, so print the stdout and stderr for debuging.
+                if !out.stderr.is_empty() {
+                    let stdout = String::from_utf8(out.stdout)
+                        .expect("rustc error contained non-UTF8 characters.");
+                    let stderr = String::from_utf8(out.stderr)
+                        .expect("rustc error contained non-UTF8 characters.");
+                    panic!("stdout:\n{stdout}\nstderr:\n{stderr}");
+                }
+            }
+            #[test]
+            fn release() {
+                build(&["-O"], concat!("./", stringify!($test_name), ".dll"));
+            }
+            #[test]
+            fn debug() {
+                build(&[], concat!("./debug_", stringify!($test_name), ".dll"));
+            }
+        }
+    };
+}
 macro_rules! cargo_test {
     ($test_name:ident) => {
         mod $test_name{
@@ -426,8 +467,18 @@ run_test! {types,enums}
 run_test! {types,nbody}
 run_test! {types,structs}
 run_test! {types,interop}
+run_test! {types,managed_calls}
+run_test! {types,managed_fields}
+run_test! {types,gc_handles}
+run_test! {types,managed_arrays}
+run_test! {types,try_managed}
+run_test! {types,delegates}
+run_test! {types,virtuals}
+test_cdylib! {types,exported_virtuals}
+run_test! {types,generics}
 run_test! {types,vec}
 run_test! {types,string_slice}
+run_test! {types,strings}
 run_test! {types,slice}
 run_test! {std,main}
 run_test! {control_flow,cf_for}
@@ -437,37 +488,6 @@ cargo_test! {glam_test}
 cargo_test! {fastrand_test}
 use lazy_static::*;
 lazy_static! {
-    static ref RUNTIME_CONFIG: String = {
-        let info = std::process::Command::new("dotnet")
-            .arg("--info")
-            .output()
-            .expect("Could not run `dotnet --info`");
-        if info.stderr.len() > 0 {
-            let stderr = std::str::from_utf8(&info.stderr).expect("Error message not utf8");
-            panic!("dotnet --info panicked with {stderr}")
-        }
-        let info = std::str::from_utf8(&info.stdout).expect("Error message not utf8");
-        let version_start = info.find("Host:").unwrap_or_default();
-        let version_start = version_start + &info[version_start..].find("Version:").unwrap();
-        let version_start = version_start + "Version:".len();
-        let version_end = info.find("Architecture:").unwrap();
-        let version = &info[version_start..version_end].trim();
-        format!(
-            "{{
-            \"runtimeOptions\": {{
-              \"tfm\": \"netcoreapp3.1\",
-              \"framework\": {{
-                \"name\": \"Microsoft.NETCore.App\",
-                \"version\": \"{version}\"
-              }},
-              \"configProperties\": {{
-                \"System.Threading.ThreadPool.MinThreads\": 4,
-                \"System.Threading.ThreadPool.MaxThreads\": 25
-              }}
-            }}
-          }}"
-        )
-    };
     static ref IS_MONO_PRESENT: bool = std::process::Command::new("mono").output().is_ok();
     static ref IS_DOTNET_PRESENT: bool = std::process::Command::new("dotnet").output().is_ok();
     static ref RUSTC_BUILD_STATUS: Result<(), String> = build_backend();
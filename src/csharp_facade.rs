@@ -0,0 +1,281 @@
+use crate::{
+    access_modifier::AccessModifer,
+    assembly::Assembly,
+    method::Method,
+    r#type::{DotnetTypeRef, Type},
+    type_def::TypeDef,
+};
+use std::fmt::Write;
+/// Support types every facade relies on.
+const FACADE_PRELUDE: &str = "// Generated by rustc_codegen_clr. Requires `<AllowUnsafeBlocks>true</AllowUnsafeBlocks>`.
+using System;
+/// Thrown when a Rust function returns an `Err` value.
+public sealed class RustException<E> : Exception
+{
+    public E Error { get; }
+    public RustException(E error) : base($\"Rust function returned an error: {error}\") { Error = error; }
+}
+";
+/// Generates C# source of wrappers around public methods of `asm`, which use types idiomatic in C#. Slices are passed as `ReadOnlySpan<T>`,
+/// `Option<T>` is returned as `T?` and `Err` values of a `Result` are thrown as a `RustException<E>`. `str`s are not lowered to
+/// `RustStr` yet, so methods taking them get no special treatment.
+/// Only methods which need such conversions get a wrapper, since other methods are already usable from C#.
+pub fn csharp_facade(asm: &Assembly) -> String {
+    let mut types: Vec<&TypeDef> = asm
+        .types()
+        .filter(|tpe| tpe.access_modifier() == AccessModifer::Public && tpe.gargc() == 0)
+        .collect();
+    types.sort_by(|a, b| a.name().cmp(b.name()));
+    let mut out = FACADE_PRELUDE.to_owned();
+    for tpe in types {
+        let mut methods: Vec<&Method> = tpe
+            .methods()
+            .filter(|method| method.access() == AccessModifer::Public && needs_wrapper(method))
+            // Property getters can't be called by name from C#.
+            .filter(|method| {
+                !tpe.properties()
+                    .iter()
+                    .any(|(_, _, getter)| getter.as_ref() == method.name())
+            })
+            .collect();
+        if methods.is_empty() {
+            continue;
+        }
+        methods.sort_by(|a, b| a.name().cmp(b.name()));
+        let (namespace, class_name) = match tpe.name().rsplit_once('.') {
+            Some((namespace, class_name)) => (Some(namespace), class_name),
+            None => (None, tpe.name()),
+        };
+        if let Some(namespace) = namespace {
+            writeln!(out, "namespace {namespace}\n{{").unwrap();
+        }
+        writeln!(
+            out,
+            "public static unsafe partial class {class_name}Facade\n{{"
+        )
+        .unwrap();
+        for method in methods {
+            wrapper_cs(&mut out, tpe, method);
+        }
+        writeln!(out, "}}").unwrap();
+        if namespace.is_some() {
+            writeln!(out, "}}").unwrap();
+        }
+    }
+    out
+}
+/// How a Rust argument is passed from C#.
+enum ArgKind {
+    /// Passed as-is.
+    Plain(Type),
+    /// `&[T]` passed as a `ReadOnlySpan<T>`.
+    Slice(Type),
+}
+impl ArgKind {
+    fn from_type(tpe: &Type) -> Self {
+        if let Type::Ptr(inner) = tpe {
+            if let Type::DotnetType(dotnet) = inner.as_ref() {
+                if let ("RustSlice", [element]) = (dotnet.name_path(), dotnet.generics()) {
+                    return Self::Slice(element.clone());
+                }
+            }
+        }
+        Self::Plain(tpe.clone())
+    }
+    fn cs_type(&self) -> String {
+        match self {
+            Self::Plain(tpe) => cs_type(tpe),
+            Self::Slice(element) => format!("ReadOnlySpan<{}>", cs_type(element)),
+        }
+    }
+}
+/// How a Rust return value is returned to C#.
+enum ReturnKind {
+    Plain(Type),
+    /// `Option<T>`, returned as `T?`.
+    Option(Type),
+    /// `Result<T, E>`, returned as `T`, with the error thrown as an exception.
+    Result(Type, Type),
+}
+impl ReturnKind {
+    fn from_type(tpe: &Type) -> Self {
+        if let Type::DotnetType(dotnet) = tpe {
+            match (dotnet.name_path(), dotnet.generics()) {
+                ("core.option.Option", [some]) => return Self::Option(some.clone()),
+                ("core.result.Result", [ok, err]) => return Self::Result(ok.clone(), err.clone()),
+                _ => (),
+            }
+        }
+        Self::Plain(tpe.clone())
+    }
+    fn cs_type(&self) -> String {
+        match self {
+            Self::Plain(tpe) => cs_type(tpe),
+            Self::Option(some) => format!("{}?", cs_type(some)),
+            Self::Result(ok, _) => cs_type(ok),
+        }
+    }
+}
+/// Checks if `method` takes or returns a type which has a more idiomatic C# counterpart.
+fn needs_wrapper(method: &Method) -> bool {
+    method
+        .sig()
+        .inputs()
+        .iter()
+        .any(|arg| !matches!(ArgKind::from_type(arg), ArgKind::Plain(_)))
+        || !matches!(
+            ReturnKind::from_type(method.sig().output()),
+            ReturnKind::Plain(_)
+        )
+}
+/// Writes a wrapper around `method` of type `owner`. Instance methods are wrapped by extension methods.
+fn wrapper_cs(out: &mut String, owner: &TypeDef, method: &Method) {
+    let owner_name = cs_name(owner.name());
+    let args: Vec<ArgKind> = method
+        .explicit_inputs()
+        .iter()
+        .map(ArgKind::from_type)
+        .collect();
+    let ret = ReturnKind::from_type(method.sig().output());
+    let mut params: Vec<String> = args
+        .iter()
+        .enumerate()
+        .map(|(idx, arg)| format!("{} a{idx}", arg.cs_type()))
+        .collect();
+    let target = if method.is_static() {
+        format!("{owner_name}.{name}", name = method.name())
+    } else {
+        params.insert(0, format!("this ref {owner_name} self"));
+        format!("self.{name}", name = method.name())
+    };
+    let ret_type = ret.cs_type();
+    writeln!(
+        out,
+        "\tpublic static {ret_type} {name}({params})\n\t{{",
+        name = method.name(),
+        params = params.join(", ")
+    )
+    .unwrap();
+    let mut call_args = Vec::with_capacity(args.len());
+    for (idx, arg) in args.iter().enumerate() {
+        match arg {
+            ArgKind::Plain(_) => call_args.push(format!("a{idx}")),
+            ArgKind::Slice(element) => {
+                let element = cs_type(element);
+                writeln!(out, "\t\tfixed ({element}* p{idx} = a{idx})").unwrap();
+                writeln!(out, "\t\t{{").unwrap();
+                writeln!(out, "\t\tvar s{idx} = new RustSlice<{element}> {{ _ptr = p{idx}, _length = (nuint)a{idx}.Length }};").unwrap();
+                call_args.push(format!("&s{idx}"));
+            }
+        }
+    }
+    let call = format!("{target}({args})", args = call_args.join(", "));
+    match &ret {
+        ReturnKind::Plain(Type::Void) => writeln!(out, "\t\t{call};").unwrap(),
+        ReturnKind::Plain(_) => writeln!(out, "\t\treturn {call};").unwrap(),
+        // Variant 0 of `Option` is `None`, and variant 0 of `Result` is `Ok`.
+        ReturnKind::Option(_) => {
+            writeln!(out, "\t\tvar result = {call};").unwrap();
+            writeln!(
+                out,
+                "\t\treturn result._tag == 0 ? ({ret_type})null : result.v_Some.m_0;"
+            )
+            .unwrap();
+        }
+        ReturnKind::Result(ok, err) => {
+            writeln!(out, "\t\tvar result = {call};").unwrap();
+            writeln!(
+                out,
+                "\t\tif (result._tag != 0) throw new RustException<{err}>(result.v_Err.m_0);",
+                err = cs_type(err)
+            )
+            .unwrap();
+            if *ok != Type::Void {
+                writeln!(out, "\t\treturn result.v_Ok.m_0;").unwrap();
+            }
+        }
+    }
+    let fixed_blocks = args
+        .iter()
+        .filter(|arg| !matches!(arg, ArgKind::Plain(_)))
+        .count();
+    for _ in 0..fixed_blocks {
+        writeln!(out, "\t\t}}").unwrap();
+    }
+    writeln!(out, "\t}}").unwrap();
+}
+/// Converts a CIL type name(`my_lib.Point`, `Outer/Inner`) into a C# one.
+fn cs_name(name: &str) -> String {
+    name.replace('/', ".")
+}
+/// Gets the C# name of type `tpe`.
+fn cs_type(tpe: &Type) -> String {
+    match tpe {
+        Type::Void => "void".into(),
+        Type::Bool => "bool".into(),
+        Type::DotnetChar => "char".into(),
+        Type::I8 => "sbyte".into(),
+        Type::U8 => "byte".into(),
+        Type::I16 => "short".into(),
+        Type::U16 => "ushort".into(),
+        Type::I32 => "int".into(),
+        Type::U32 => "uint".into(),
+        Type::I64 => "long".into(),
+        Type::U64 => "ulong".into(),
+        Type::I128 => "System.Int128".into(),
+        Type::U128 => "System.UInt128".into(),
+        Type::ISize => "nint".into(),
+        Type::USize => "nuint".into(),
+        Type::F32 => "float".into(),
+        Type::F64 => "double".into(),
+        // Pointers to `void` are pointers to `RustVoid` in CIL.
+        Type::Ptr(inner) if **inner == Type::Void => "RustVoid*".into(),
+        Type::Ptr(inner) => format!("{}*", cs_type(inner)),
+        Type::DotnetType(dotnet) => dotnet_type_cs(dotnet),
+        Type::DotnetArray(array) => format!(
+            "{element}[{dims}]",
+            element = cs_type(&array.element),
+            dims = ",".repeat(array.dimensions.saturating_sub(1) as usize)
+        ),
+        Type::GenericArg(idx) => format!("G{idx}"),
+        Type::Unresolved => "Unresolved".into(),
+        Type::Foreign => "Foreign".into(),
+        Type::FnDef(_) => "FnDef".into(),
+        Type::PinnedRef(inner) => format!("ref {}", cs_type(inner)),
+    }
+}
+fn dotnet_type_cs(dotnet: &DotnetTypeRef) -> String {
+    let name = cs_name(dotnet.name_path());
+    if dotnet.generics().is_empty() {
+        return name;
+    }
+    let generics: Vec<String> = dotnet.generics().iter().map(cs_type).collect();
+    format!("{name}<{}>", generics.join(", "))
+}
+#[test]
+fn slice_wrapper() {
+    use crate::function_sig::FnSig;
+    let mut slice = DotnetTypeRef::new(None, "RustSlice");
+    slice.set_generics([Type::I32]);
+    let mut option = DotnetTypeRef::new(None, "core.option.Option");
+    option.set_generics([Type::I32]);
+    let sig = FnSig::new(
+        &[Type::Ptr(Box::new(Type::DotnetType(Box::new(slice))))],
+        &Type::DotnetType(Box::new(option)),
+    );
+    let mut class = TypeDef::nameonly("my_lib.MyLib");
+    class.add_method(Method::new(
+        AccessModifer::Public,
+        true,
+        sig,
+        "first",
+        vec![],
+    ));
+    let mut asm = Assembly::empty();
+    asm.add_typedef(class);
+    let facade = csharp_facade(&asm);
+    assert!(facade.contains("namespace my_lib"));
+    assert!(facade.contains("public static unsafe partial class MyLibFacade"));
+    assert!(facade.contains("public static int? first(ReadOnlySpan<int> a0)"));
+    assert!(facade.contains("my_lib.MyLib.first(&s0)"));
+}
@@ -0,0 +1,197 @@
+use crate::{
+    cil_op::{CILOp, CallSite},
+    method::Method,
+    r#type::{DotnetTypeRef, Type},
+    type_def::TypeDef,
+};
+use std::collections::{HashMap, HashSet};
+/// Names of methods and types reachable from a set of roots.
+#[derive(Default, Debug)]
+pub struct Reachable {
+    methods: HashSet<String>,
+    types: HashSet<String>,
+    method_queue: Vec<String>,
+    type_queue: Vec<String>,
+}
+impl Reachable {
+    /// Checks if a method named `name` is reachable.
+    pub fn has_method(&self, name: &str) -> bool {
+        self.methods.contains(name)
+    }
+    /// Checks if a type named `name` is reachable.
+    pub fn has_type(&self, name: &str) -> bool {
+        self.types.contains(name)
+    }
+    /// Marks method `name`(belonging to the `<Module>` class) as reachable.
+    pub fn add_method(&mut self, name: &str) {
+        if self.methods.insert(name.into()) {
+            self.method_queue.push(name.into());
+        }
+    }
+    /// Marks type `name` as reachable.
+    pub fn add_type_name(&mut self, name: &str) {
+        // Nested types(eg. enum variants) are defined within their parrent, so the parrent is what needs to be kept.
+        let name = name.split('/').next().unwrap_or(name);
+        if self.types.insert(name.into()) {
+            self.type_queue.push(name.into());
+        }
+    }
+    /// Walks all methods and types reachable from the roots added so far.
+    pub fn walk<'a>(
+        &mut self,
+        methods: impl Iterator<Item = &'a Method>,
+        types: impl Iterator<Item = &'a TypeDef>,
+    ) {
+        let mut methods_by_name: HashMap<&str, Vec<&Method>> = HashMap::new();
+        for method in methods {
+            methods_by_name
+                .entry(method.name())
+                .or_default()
+                .push(method);
+        }
+        let types_by_name: HashMap<&str, &TypeDef> = types.map(|tpe| (tpe.name(), tpe)).collect();
+        loop {
+            if let Some(method) = self.method_queue.pop() {
+                for method in methods_by_name.get(method.as_str()).into_iter().flatten() {
+                    self.visit_method(method);
+                }
+            } else if let Some(tpe) = self.type_queue.pop() {
+                if let Some(tpe) = types_by_name.get(tpe.as_str()) {
+                    self.visit_type_def(tpe);
+                }
+            } else {
+                break;
+            }
+        }
+    }
+    fn visit_method(&mut self, method: &Method) {
+        self.visit_types(method.sig().inputs());
+        self.visit_type(method.sig().output());
+        self.visit_types(method.locals());
+        for op in method.get_ops() {
+            self.visit_op(op);
+        }
+    }
+    fn visit_type_def(&mut self, type_def: &TypeDef) {
+        if let Some(extends) = type_def.extends() {
+            self.visit_dotnet_type(extends);
+        }
+        for interface in type_def.implements() {
+            self.visit_dotnet_type(interface);
+        }
+        for (_, field_type) in type_def.fields() {
+            self.visit_type(field_type);
+        }
+        for inner_type in type_def.inner_types() {
+            self.visit_type_def(inner_type);
+        }
+        for method in type_def.methods() {
+            self.visit_method(method);
+        }
+    }
+    fn visit_op(&mut self, op: &CILOp) {
+        match op {
+            CILOp::Call(site)
+            | CILOp::CallVirt(site)
+            | CILOp::TailCall(site)
+            | CILOp::LDFtn(site)
+            | CILOp::NewObj(site) => self.visit_call_site(site),
+            CILOp::LDField(field) | CILOp::LDFieldAdress(field) | CILOp::STField(field) => {
+                self.visit_dotnet_type(field.owner());
+                self.visit_type(field.tpe());
+            }
+            CILOp::LDStaticField(field) | CILOp::STStaticField(field) => {
+                if let Some(owner) = field.owner() {
+                    self.visit_dotnet_type(owner);
+                }
+                self.visit_type(field.tpe());
+            }
+            CILOp::NewTMPLocal(tpe)
+            | CILOp::LdObj(tpe)
+            | CILOp::Box(tpe)
+            | CILOp::UnboxAny(tpe)
+            | CILOp::CastClass(tpe)
+            | CILOp::STObj(tpe)
+            | CILOp::SizeOf(tpe) => self.visit_type(tpe),
+            CILOp::BeginCatch(class) => self.visit_dotnet_type(class),
+            CILOp::CallI(sig) => {
+                self.visit_types(sig.inputs());
+                self.visit_type(sig.output());
+            }
+            CILOp::NewArr(array)
+            | CILOp::LDLen(array)
+            | CILOp::LDElem(array)
+            | CILOp::STElem(array)
+            | CILOp::LDElemAdress(array) => self.visit_type(&array.element),
+            _ => (),
+        }
+    }
+    fn visit_call_site(&mut self, site: &CallSite) {
+        match site.class() {
+            Some(class) => self.visit_dotnet_type(class),
+            None => self.add_method(site.name()),
+        }
+        self.visit_types(site.inputs());
+        self.visit_type(site.signature().output());
+    }
+    fn visit_types(&mut self, types: &[Type]) {
+        for tpe in types {
+            self.visit_type(tpe);
+        }
+    }
+    fn visit_type(&mut self, tpe: &Type) {
+        match tpe {
+            // Those types are emmited as references to types inserted by `libc`.
+            Type::Void => self.add_type_name("RustVoid"),
+            Type::Unresolved => self.add_type_name("Unresolved"),
+            Type::Foreign => self.add_type_name("Foreign"),
+            Type::Ptr(inner) | Type::PinnedRef(inner) => self.visit_type(inner),
+            Type::DotnetType(dotnet) => self.visit_dotnet_type(dotnet),
+            Type::DotnetArray(array) => self.visit_type(&array.element),
+            Type::FnDef(site) => self.visit_call_site(site),
+            _ => (),
+        }
+    }
+    fn visit_dotnet_type(&mut self, dotnet: &DotnetTypeRef) {
+        // Types from other assemblies are not defined here.
+        if dotnet.asm().is_none() {
+            self.add_type_name(dotnet.name_path());
+        }
+        self.visit_types(dotnet.generics());
+    }
+}
+#[test]
+fn unreachable_removed() {
+    use crate::{
+        access_modifier::AccessModifer, assembly::Assembly, function_sig::FnSig, method::Attribute,
+    };
+    let sig = FnSig::new(&[], &Type::Void);
+    let mut entry = Method::new(AccessModifer::Private, true, sig.clone(), "entry", vec![]);
+    entry.add_attribute(Attribute::EntryPoint);
+    entry.set_ops(vec![CILOp::Call(CallSite::boxed(
+        None,
+        "used".into(),
+        sig.clone(),
+        true,
+    ))]);
+    let used = Method::new(
+        AccessModifer::Private,
+        true,
+        sig.clone(),
+        "used",
+        vec![Type::DotnetType(DotnetTypeRef::new(None, "Used").into())],
+    );
+    let unused = Method::new(AccessModifer::Public, true, sig, "unused", vec![]);
+    let mut asm = Assembly::empty();
+    asm.add_method(entry);
+    asm.add_method(used);
+    asm.add_method(unused);
+    asm.add_typedef(TypeDef::nameonly("Used"));
+    asm.add_typedef(TypeDef::nameonly("Unused"));
+    asm.eliminate_dead_code(false);
+    let mut methods: Vec<_> = asm.methods().map(Method::name).collect();
+    methods.sort();
+    assert_eq!(methods, ["entry", "used"]);
+    let types: Vec<_> = asm.types().map(TypeDef::name).collect();
+    assert_eq!(types, ["Used"]);
+}
@@ -0,0 +1,901 @@
+//! A minimal reader of ECMA-335 metadata, contained within .NET assemblies(`.dll` files).
+use crate::IString;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+/// Error encountered while reading .NET metadata.
+#[derive(Debug)]
+pub enum MetadataError {
+    IoError(std::io::Error),
+    /// File is not a valid PE file.
+    NotAPE,
+    /// File is a PE file, but it does not contain any .NET metadata.
+    NotManaged,
+    /// Metadata is malformed - a part of it is missing or points outside of the file.
+    Malformed(&'static str),
+    /// File is a .NET module, but not an assembly - it has no assembly manifest.
+    NoAssembly,
+}
+impl From<std::io::Error> for MetadataError {
+    fn from(error: std::io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+/// Identity of an external .NET assembly, used to reference it.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct AssemblyRef {
+    name: IString,
+    version: [u16; 4],
+    culture: Option<IString>,
+    public_key_token: Option<[u8; 8]>,
+}
+impl AssemblyRef {
+    /// Creates a reference to assembly `name`, without any version or public key information.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            version: [0; 4],
+            culture: None,
+            public_key_token: None,
+        }
+    }
+    /// Reads the identity of the assembly at `path`.
+    pub fn from_dll(path: &Path) -> Result<Self, MetadataError> {
+        let bytes = std::fs::read(path)?;
+        let metadata = Metadata::from_pe(&bytes)?;
+        metadata.assembly_ref()
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Version of the assembly, in the major, minor, build, revision order.
+    pub fn version(&self) -> [u16; 4] {
+        self.version
+    }
+    pub fn culture(&self) -> Option<&str> {
+        self.culture.as_deref()
+    }
+    pub fn public_key_token(&self) -> Option<[u8; 8]> {
+        self.public_key_token
+    }
+}
+const TYPE_REF_TABLE: usize = 0x01;
+const TYPE_DEF_TABLE: usize = 0x02;
+const METHOD_DEF_TABLE: usize = 0x06;
+const PARAM_TABLE: usize = 0x08;
+/// Index of the `Assembly` metadata table.
+const ASSEMBLY_TABLE: usize = 0x20;
+const ASSEMBLY_REF_TABLE: usize = 0x23;
+/// Number of metadata tables defined by ECMA-335.
+const TABLE_COUNT: usize = 0x2D;
+/// Calling convention of methods taking variable arguments(`vararg`).
+const VARARG_CALLING_CONVENTION: u8 = 0x05;
+/// Separates fixed and variable arguments in a signature.
+const ELEMENT_TYPE_SENTINEL: u8 = 0x41;
+/// Marks an unused tag of a coded index.
+const NO_TABLE: u8 = 0xFF;
+/// A kind of a column of a metadata table.
+#[derive(Clone, Copy)]
+enum Column {
+    U16,
+    U32,
+    /// Index into the `#Strings` heap.
+    Str,
+    /// Index into the `#GUID` heap.
+    Guid,
+    /// Index into the `#Blob` heap.
+    Blob,
+    /// Index into a table.
+    Table(u8),
+    /// Index into one of many tables, with the table selected by the lowest `bits` bits.
+    Coded(&'static [u8], u32),
+}
+const TYPE_DEF_OR_REF: Column = Column::Coded(&[0x02, 0x01, 0x1B], 2);
+const HAS_CONSTANT: Column = Column::Coded(&[0x04, 0x08, 0x17], 2);
+const HAS_CUSTOM_ATTRIBUTE: Column = Column::Coded(
+    &[
+        0x06, 0x04, 0x01, 0x02, 0x08, 0x09, 0x0A, 0x00, 0x0E, 0x17, 0x14, 0x11, 0x1A, 0x1B, 0x20,
+        0x23, 0x26, 0x27, 0x28, 0x2A, 0x2C, 0x2B,
+    ],
+    5,
+);
+const HAS_FIELD_MARSHAL: Column = Column::Coded(&[0x04, 0x08], 1);
+const HAS_DECL_SECURITY: Column = Column::Coded(&[0x02, 0x06, 0x20], 2);
+const MEMBER_REF_PARENT: Column = Column::Coded(&[0x02, 0x01, 0x1A, 0x06, 0x1B], 3);
+const HAS_SEMANTICS: Column = Column::Coded(&[0x14, 0x17], 1);
+const METHOD_DEF_OR_REF: Column = Column::Coded(&[0x06, 0x0A], 1);
+const MEMBER_FORWARDED: Column = Column::Coded(&[0x04, 0x06], 1);
+const IMPLEMENTATION: Column = Column::Coded(&[0x26, 0x23, 0x27], 2);
+const CUSTOM_ATTRIBUTE_TYPE: Column = Column::Coded(&[NO_TABLE, NO_TABLE, 0x06, 0x0A, NO_TABLE], 3);
+const RESOLUTION_SCOPE: Column = Column::Coded(&[0x00, 0x1A, 0x23, 0x01], 2);
+const TYPE_OR_METHOD_DEF: Column = Column::Coded(&[0x02, 0x06], 1);
+/// Layout of all the metadata tables, in order.
+const TABLE_SCHEMA: [&[Column]; TABLE_COUNT] = {
+    use Column::*;
+    [
+        // Module
+        &[U16, Str, Guid, Guid, Guid],
+        // TypeRef
+        &[RESOLUTION_SCOPE, Str, Str],
+        // TypeDef
+        &[U32, Str, Str, TYPE_DEF_OR_REF, Table(0x04), Table(0x06)],
+        // FieldPtr
+        &[Table(0x04)],
+        // Field
+        &[U16, Str, Blob],
+        // MethodPtr
+        &[Table(0x06)],
+        // MethodDef
+        &[U32, U16, U16, Str, Blob, Table(0x08)],
+        // ParamPtr
+        &[Table(0x08)],
+        // Param
+        &[U16, U16, Str],
+        // InterfaceImpl
+        &[Table(0x02), TYPE_DEF_OR_REF],
+        // MemberRef
+        &[MEMBER_REF_PARENT, Str, Blob],
+        // Constant(the type is 1 byte, followed by 1 byte of padding)
+        &[U16, HAS_CONSTANT, Blob],
+        // CustomAttribute
+        &[HAS_CUSTOM_ATTRIBUTE, CUSTOM_ATTRIBUTE_TYPE, Blob],
+        // FieldMarshal
+        &[HAS_FIELD_MARSHAL, Blob],
+        // DeclSecurity
+        &[U16, HAS_DECL_SECURITY, Blob],
+        // ClassLayout
+        &[U16, U32, Table(0x02)],
+        // FieldLayout
+        &[U32, Table(0x04)],
+        // StandAloneSig
+        &[Blob],
+        // EventMap
+        &[Table(0x02), Table(0x14)],
+        // EventPtr
+        &[Table(0x14)],
+        // Event
+        &[U16, Str, TYPE_DEF_OR_REF],
+        // PropertyMap
+        &[Table(0x02), Table(0x17)],
+        // PropertyPtr
+        &[Table(0x17)],
+        // Property
+        &[U16, Str, Blob],
+        // MethodSemantics
+        &[U16, Table(0x06), HAS_SEMANTICS],
+        // MethodImpl
+        &[Table(0x02), METHOD_DEF_OR_REF, METHOD_DEF_OR_REF],
+        // ModuleRef
+        &[Str],
+        // TypeSpec
+        &[Blob],
+        // ImplMap
+        &[U16, MEMBER_FORWARDED, Str, Table(0x1A)],
+        // FieldRVA
+        &[U32, Table(0x04)],
+        // EncLog
+        &[U32, U32],
+        // EncMap
+        &[U32],
+        // Assembly
+        &[U32, U16, U16, U16, U16, U32, Blob, Str, Str],
+        // AssemblyProcessor
+        &[U32],
+        // AssemblyOS
+        &[U32, U32, U32],
+        // AssemblyRef
+        &[U16, U16, U16, U16, U32, Blob, Str, Str, Blob],
+        // AssemblyRefProcessor
+        &[U32, Table(0x23)],
+        // AssemblyRefOS
+        &[U32, U32, U32, Table(0x23)],
+        // File
+        &[U32, Str, Blob],
+        // ExportedType
+        &[U32, U32, Str, Str, IMPLEMENTATION],
+        // ManifestResource
+        &[U32, U32, Str, IMPLEMENTATION],
+        // NestedClass
+        &[Table(0x02), Table(0x02)],
+        // GenericParam
+        &[U16, U16, TYPE_OR_METHOD_DEF, Str],
+        // MethodSpec
+        &[METHOD_DEF_OR_REF, Blob],
+        // GenericParamConstraint
+        &[Table(0x2A), TYPE_DEF_OR_REF],
+    ]
+};
+/// Reads a little-endian u16 at `offset`.
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, MetadataError> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or(MetadataError::Malformed("unexpected end of file"))
+}
+/// Reads a little-endian u32 at `offset`.
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, MetadataError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or(MetadataError::Malformed("unexpected end of file"))
+}
+/// Metadata of a .NET module.
+pub struct Metadata<'a> {
+    strings: &'a [u8],
+    blobs: &'a [u8],
+    /// Contents of the `#~` stream, containg all the tables.
+    tables: &'a [u8],
+    row_counts: [u32; TABLE_COUNT],
+    /// Offset of each table within the `#~` stream.
+    table_offsets: [usize; TABLE_COUNT],
+    wide_strings: bool,
+    wide_guids: bool,
+    wide_blobs: bool,
+}
+impl<'a> Metadata<'a> {
+    /// Finds and parses the metadata within PE file `pe`.
+    pub fn from_pe(pe: &'a [u8]) -> Result<Self, MetadataError> {
+        if pe.get(0..2) != Some(b"MZ") {
+            return Err(MetadataError::NotAPE);
+        }
+        let pe_header = read_u32(pe, 0x3C)? as usize;
+        if pe.get(pe_header..pe_header + 4) != Some(b"PE\0\0") {
+            return Err(MetadataError::NotAPE);
+        }
+        let coff_header = pe_header + 4;
+        let section_count = read_u16(pe, coff_header + 2)? as usize;
+        let optional_header_size = read_u16(pe, coff_header + 16)? as usize;
+        let optional_header = coff_header + 20;
+        let data_directories = match read_u16(pe, optional_header)? {
+            // PE32
+            0x10B => optional_header + 96,
+            // PE32+
+            0x20B => optional_header + 112,
+            _ => return Err(MetadataError::NotAPE),
+        };
+        // The CLI header is described by the 15th data directory.
+        let cli_header_rva = read_u32(pe, data_directories + 14 * 8)?;
+        if cli_header_rva == 0 {
+            return Err(MetadataError::NotManaged);
+        }
+        let sections = optional_header + optional_header_size;
+        let rva_to_offset = |rva: u32| -> Result<usize, MetadataError> {
+            for section in 0..section_count {
+                let section = sections + section * 40;
+                let virtual_size = read_u32(pe, section + 8)?;
+                let virtual_address = read_u32(pe, section + 12)?;
+                let raw_size = read_u32(pe, section + 16)?;
+                let raw_pointer = read_u32(pe, section + 20)?;
+                if rva >= virtual_address && rva < virtual_address + virtual_size.max(raw_size) {
+                    return Ok((rva - virtual_address + raw_pointer) as usize);
+                }
+            }
+            Err(MetadataError::Malformed("RVA outside of all sections"))
+        };
+        let cli_header = rva_to_offset(cli_header_rva)?;
+        let metadata_start = rva_to_offset(read_u32(pe, cli_header + 8)?)?;
+        let metadata_size = read_u32(pe, cli_header + 12)? as usize;
+        let metadata = pe
+            .get(metadata_start..metadata_start + metadata_size)
+            .ok_or(MetadataError::Malformed("metadata outside of file"))?;
+        Self::from_metadata_root(metadata)
+    }
+    /// Parses the metadata starting at the metadata root.
+    fn from_metadata_root(metadata: &'a [u8]) -> Result<Self, MetadataError> {
+        if read_u32(metadata, 0)? != 0x424A_5342 {
+            return Err(MetadataError::Malformed("bad metadata signature"));
+        }
+        let version_len = read_u32(metadata, 12)? as usize;
+        let streams_header = 16 + version_len;
+        let stream_count = read_u16(metadata, streams_header + 2)?;
+        let mut stream_header = streams_header + 4;
+        let (mut strings, mut blobs, mut tables): (&[u8], &[u8], Option<&[u8]>) = (&[], &[], None);
+        for _ in 0..stream_count {
+            let offset = read_u32(metadata, stream_header)? as usize;
+            let size = read_u32(metadata, stream_header + 4)? as usize;
+            let name_start = stream_header + 8;
+            let name = metadata.get(name_start..).ok_or(MetadataError::Malformed(
+                "stream header outside of metadata",
+            ))?;
+            let name_len = name
+                .iter()
+                .position(|byte| *byte == 0)
+                .ok_or(MetadataError::Malformed("unterminated stream name"))?;
+            let name = &name[..name_len];
+            // Names are null terminated, and padded to 4 bytes.
+            stream_header = name_start + (name_len + 4) / 4 * 4;
+            let stream = metadata
+                .get(offset..offset + size)
+                .ok_or(MetadataError::Malformed("stream outside of metadata"))?;
+            match name {
+                b"#Strings" => strings = stream,
+                b"#Blob" => blobs = stream,
+                b"#~" | b"#-" => tables = Some(stream),
+                _ => (),
+            }
+        }
+        let tables = tables.ok_or(MetadataError::Malformed("no table stream"))?;
+        let heap_sizes = *tables
+            .get(6)
+            .ok_or(MetadataError::Malformed("table stream too short"))?;
+        let valid = u64::from(read_u32(tables, 8)?) | (u64::from(read_u32(tables, 12)?) << 32);
+        let mut row_counts = [0; TABLE_COUNT];
+        let mut offset = 24;
+        for (table, row_count) in row_counts.iter_mut().enumerate() {
+            if valid & (1 << table) != 0 {
+                *row_count = read_u32(tables, offset)?;
+                offset += 4;
+            }
+        }
+        // Tables past the ones defined by ECMA-335 can't be skipped, since their layout is unknown.
+        if valid >> TABLE_COUNT != 0 {
+            return Err(MetadataError::Malformed("unknown metadata table"));
+        }
+        // Some metadata writers store 4 additional bytes after the row counts.
+        if heap_sizes & 0x40 != 0 {
+            offset += 4;
+        }
+        let mut metadata = Self {
+            strings,
+            blobs,
+            tables,
+            row_counts,
+            table_offsets: [0; TABLE_COUNT],
+            wide_strings: heap_sizes & 0x01 != 0,
+            wide_guids: heap_sizes & 0x02 != 0,
+            wide_blobs: heap_sizes & 0x04 != 0,
+        };
+        for table in 0..TABLE_COUNT {
+            metadata.table_offsets[table] = offset;
+            offset += metadata.row_size(table) * metadata.row_counts[table] as usize;
+        }
+        Ok(metadata)
+    }
+    /// Number of rows in table `table`.
+    pub fn row_count(&self, table: usize) -> u32 {
+        self.row_counts[table]
+    }
+    fn column_size(&self, column: Column) -> usize {
+        let wide = match column {
+            Column::U16 => false,
+            Column::U32 => true,
+            Column::Str => self.wide_strings,
+            Column::Guid => self.wide_guids,
+            Column::Blob => self.wide_blobs,
+            Column::Table(table) => self.row_counts[table as usize] >= 1 << 16,
+            Column::Coded(tables, bits) => tables
+                .iter()
+                .filter(|table| **table != NO_TABLE)
+                .any(|table| self.row_counts[*table as usize] >= 1 << (16 - bits)),
+        };
+        if wide {
+            4
+        } else {
+            2
+        }
+    }
+    fn row_size(&self, table: usize) -> usize {
+        TABLE_SCHEMA[table]
+            .iter()
+            .map(|column| self.column_size(*column))
+            .sum()
+    }
+    /// Reads the value of column `column` in row `row`(indexed from 0) of table `table`. Indices are returned as-is, without being resolved.
+    pub fn column(&self, table: usize, row: u32, column: usize) -> Result<u32, MetadataError> {
+        let schema = TABLE_SCHEMA[table];
+        let offset = self.table_offsets[table]
+            + self.row_size(table) * row as usize
+            + schema[..column]
+                .iter()
+                .map(|column| self.column_size(*column))
+                .sum::<usize>();
+        if self.column_size(schema[column]) == 4 {
+            read_u32(self.tables, offset)
+        } else {
+            read_u16(self.tables, offset).map(u32::from)
+        }
+    }
+    /// Reads a null-terminated string at `index` in the `#Strings` heap.
+    pub fn string(&self, index: u32) -> Result<&'a str, MetadataError> {
+        let start = self
+            .strings
+            .get(index as usize..)
+            .ok_or(MetadataError::Malformed("string index out of bounds"))?;
+        let len = start
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or(MetadataError::Malformed("unterminated string"))?;
+        std::str::from_utf8(&start[..len]).map_err(|_| MetadataError::Malformed("invalid UTF-8"))
+    }
+    /// Reads a blob at `index` in the `#Blob` heap.
+    pub fn blob(&self, index: u32) -> Result<&'a [u8], MetadataError> {
+        let start = self
+            .blobs
+            .get(index as usize..)
+            .ok_or(MetadataError::Malformed("blob index out of bounds"))?;
+        // Blob lengths are encoded the same way as compressed integers in signatures.
+        let mut len_size = 0;
+        let len = read_compressed(start, &mut len_size)
+            .map_err(|_| MetadataError::Malformed("bad blob length"))? as usize;
+        start
+            .get(len_size..len_size + len)
+            .ok_or(MetadataError::Malformed("blob out of bounds"))
+    }
+    /// Reads all types defined within this module, together with their methods.
+    pub fn type_defs(&self) -> Result<Vec<TypeDefInfo>, MetadataError> {
+        let type_count = self.row_count(TYPE_DEF_TABLE);
+        let method_count = self.row_count(METHOD_DEF_TABLE);
+        (0..type_count)
+            .map(|row| {
+                let column = |column| self.column(TYPE_DEF_TABLE, row, column);
+                let name = ManagedTypeName {
+                    assembly: None,
+                    path: self.type_path(column(2)?, column(1)?)?,
+                };
+                // Methods of a type last until the method list of the next one.
+                let methods_start = column(5)?;
+                let methods_end = if row + 1 < type_count {
+                    self.column(TYPE_DEF_TABLE, row + 1, 5)?
+                } else {
+                    method_count + 1
+                };
+                let methods = (methods_start..methods_end)
+                    .filter_map(|method| self.method_def(method - 1).transpose())
+                    .collect::<Result<_, _>>()?;
+                Ok(TypeDefInfo {
+                    name,
+                    extends: self.type_def_or_ref(column(3)?)?,
+                    flags: column(0)?,
+                    methods,
+                })
+            })
+            .collect()
+    }
+    /// Reads method `row`(indexed from 0) of the `MethodDef` table. Returns [`None`] for methods taking variable arguments.
+    fn method_def(&self, row: u32) -> Result<Option<MethodDefInfo>, MetadataError> {
+        let column = |column| self.column(METHOD_DEF_TABLE, row, column);
+        let sig = self.blob(column(4)?)?;
+        let mut pos = 1;
+        let calling_convention = *sig
+            .first()
+            .ok_or(MetadataError::Malformed("empty method signature"))?;
+        if calling_convention & 0x0F == VARARG_CALLING_CONVENTION {
+            return Ok(None);
+        }
+        let is_generic = calling_convention & 0x10 != 0;
+        if is_generic {
+            read_compressed(sig, &mut pos)?;
+        }
+        let param_count = read_compressed(sig, &mut pos)?;
+        let output = self.sig_type(sig, &mut pos)?;
+        let params_start = column(5)?;
+        let params_end = if row + 1 < self.row_count(METHOD_DEF_TABLE) {
+            self.column(METHOD_DEF_TABLE, row + 1, 5)?
+        } else {
+            self.row_count(PARAM_TABLE) + 1
+        };
+        let mut params = Vec::with_capacity(param_count as usize);
+        for index in 1..=param_count {
+            // Arguments after the sentinel are the variable ones.
+            if sig.get(pos) == Some(&ELEMENT_TYPE_SENTINEL) {
+                return Ok(None);
+            }
+            let tpe = self.sig_type(sig, &mut pos)?;
+            // Parameter rows are optional, and sequence 0 describes the return value.
+            let mut name: IString = format!("arg{index}").into();
+            for param in params_start..params_end {
+                if self.column(PARAM_TABLE, param - 1, 1)? == index {
+                    name = self.string(self.column(PARAM_TABLE, param - 1, 2)?)?.into();
+                }
+            }
+            params.push((name, tpe));
+        }
+        Ok(Some(MethodDefInfo {
+            name: self.string(column(3)?)?.into(),
+            flags: column(2)? as u16,
+            params,
+            output,
+            is_generic,
+        }))
+    }
+    /// Reads a type at `pos` within signature `sig`, and moves `pos` past it.
+    fn sig_type(&self, sig: &[u8], pos: &mut usize) -> Result<SigType, MetadataError> {
+        let element_type = *sig
+            .get(*pos)
+            .ok_or(MetadataError::Malformed("signature too short"))?;
+        *pos += 1;
+        Ok(match element_type {
+            0x01 => SigType::Void,
+            0x02 => SigType::Bool,
+            0x03 => SigType::Char,
+            0x04 => SigType::I8,
+            0x05 => SigType::U8,
+            0x06 => SigType::I16,
+            0x07 => SigType::U16,
+            0x08 => SigType::I32,
+            0x09 => SigType::U32,
+            0x0A => SigType::I64,
+            0x0B => SigType::U64,
+            0x0C => SigType::F32,
+            0x0D => SigType::F64,
+            0x0E => SigType::String,
+            0x18 => SigType::ISize,
+            0x19 => SigType::USize,
+            0x1C => SigType::Object,
+            0x0F => SigType::Ptr(self.sig_type(sig, pos)?.into()),
+            0x1D => SigType::SzArray(self.sig_type(sig, pos)?.into()),
+            0x11 | 0x12 => {
+                let name = self.type_def_or_ref(read_compressed(sig, pos)?)?;
+                match name {
+                    Some(name) if element_type == 0x11 => SigType::ValueType(name),
+                    Some(name) => SigType::Class(name),
+                    None => SigType::Unsupported,
+                }
+            }
+            // By reference, pinned
+            0x10 | 0x45 => {
+                self.sig_type(sig, pos)?;
+                SigType::Unsupported
+            }
+            // Modifiers are skipped.
+            0x1F | 0x20 => {
+                read_compressed(sig, pos)?;
+                self.sig_type(sig, pos)?
+            }
+            // Generic parameters of types and methods
+            0x13 | 0x1E => {
+                read_compressed(sig, pos)?;
+                SigType::Unsupported
+            }
+            // Generic instance
+            0x15 => {
+                self.sig_type(sig, pos)?;
+                for _ in 0..read_compressed(sig, pos)? {
+                    self.sig_type(sig, pos)?;
+                }
+                SigType::Unsupported
+            }
+            // Multi-dimensional array
+            0x14 => {
+                self.sig_type(sig, pos)?;
+                read_compressed(sig, pos)?;
+                for _ in 0..read_compressed(sig, pos)? {
+                    read_compressed(sig, pos)?;
+                }
+                for _ in 0..read_compressed(sig, pos)? {
+                    read_compressed(sig, pos)?;
+                }
+                SigType::Unsupported
+            }
+            // Function pointer
+            0x1B => {
+                *pos += 1;
+                let param_count = read_compressed(sig, pos)?;
+                for _ in 0..=param_count {
+                    self.sig_type(sig, pos)?;
+                }
+                SigType::Unsupported
+            }
+            // Typed reference
+            0x16 => SigType::Unsupported,
+            _ => return Err(MetadataError::Malformed("unknown element type")),
+        })
+    }
+    /// Resolves the name of the type a `TypeDefOrRef` coded index points to. Returns [`None`] for null indices and type specs.
+    fn type_def_or_ref(&self, coded: u32) -> Result<Option<ManagedTypeName>, MetadataError> {
+        let row = coded >> 2;
+        if row == 0 {
+            return Ok(None);
+        }
+        let row = row - 1;
+        match coded & 0x3 {
+            0 => Ok(Some(ManagedTypeName {
+                assembly: None,
+                path: self.type_path(
+                    self.column(TYPE_DEF_TABLE, row, 2)?,
+                    self.column(TYPE_DEF_TABLE, row, 1)?,
+                )?,
+            })),
+            1 => self.type_ref(row).map(Some),
+            _ => Ok(None),
+        }
+    }
+    /// Resolves the name of type `row`(indexed from 0) of the `TypeRef` table.
+    fn type_ref(&self, row: u32) -> Result<ManagedTypeName, MetadataError> {
+        let scope = self.column(TYPE_REF_TABLE, row, 0)?;
+        let path = self.type_path(
+            self.column(TYPE_REF_TABLE, row, 2)?,
+            self.column(TYPE_REF_TABLE, row, 1)?,
+        )?;
+        match scope & 0x3 {
+            2 => Ok(ManagedTypeName {
+                assembly: Some(
+                    self.string(self.column(ASSEMBLY_REF_TABLE, (scope >> 2) - 1, 6)?)?
+                        .into(),
+                ),
+                path,
+            }),
+            // Nested types are referenced through the type enclosing them, and live in the same assembly.
+            3 if scope >> 2 != 0 => {
+                let enclosing = self.type_ref((scope >> 2) - 1)?;
+                Ok(ManagedTypeName {
+                    assembly: enclosing.assembly,
+                    path: format!("{}/{path}", enclosing.path).into(),
+                })
+            }
+            // Only types referenced from other assemblies are resolved.
+            _ => Ok(ManagedTypeName {
+                assembly: None,
+                path,
+            }),
+        }
+    }
+    /// Joins namespace and name(both being indices into the `#Strings` heap) of a type.
+    fn type_path(&self, namespace: u32, name: u32) -> Result<IString, MetadataError> {
+        let namespace = self.string(namespace)?;
+        let name = self.string(name)?;
+        Ok(if namespace.is_empty() {
+            name.into()
+        } else {
+            format!("{namespace}.{name}").into()
+        })
+    }
+    /// Reads the identity of the assembly from its manifest.
+    pub fn assembly_ref(&self) -> Result<AssemblyRef, MetadataError> {
+        if self.row_count(ASSEMBLY_TABLE) == 0 {
+            return Err(MetadataError::NoAssembly);
+        }
+        let column = |column| self.column(ASSEMBLY_TABLE, 0, column);
+        let version = [
+            column(1)? as u16,
+            column(2)? as u16,
+            column(3)? as u16,
+            column(4)? as u16,
+        ];
+        let public_key = self.blob(column(6)?)?;
+        let name = self.string(column(7)?)?;
+        let culture = self.string(column(8)?)?;
+        Ok(AssemblyRef {
+            name: name.into(),
+            version,
+            culture: (!culture.is_empty()).then(|| culture.into()),
+            public_key_token: (!public_key.is_empty()).then(|| public_key_token(public_key)),
+        })
+    }
+}
+/// A type, as described by a signature.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SigType {
+    Void,
+    Bool,
+    Char,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    ISize,
+    USize,
+    String,
+    Object,
+    Ptr(Box<SigType>),
+    /// A single-dimensional, zero-based array(`T[]`).
+    SzArray(Box<SigType>),
+    /// A class or an interface.
+    Class(ManagedTypeName),
+    ValueType(ManagedTypeName),
+    /// A type which has no equivalent in Rust code(a generic parameter, a type passed by reference, etc.).
+    Unsupported,
+}
+/// Full name of a type, and the assembly defining it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ManagedTypeName {
+    /// Name of the assembly defining this type, or [`None`] if it is defined in the assembly the metadata belongs to.
+    pub assembly: Option<IString>,
+    /// Namespace and name of the type, separated by a dot.
+    pub path: IString,
+}
+/// A type defined within a .NET assembly.
+#[derive(Clone, Debug)]
+pub struct TypeDefInfo {
+    pub name: ManagedTypeName,
+    /// Type this type extends, if any.
+    pub extends: Option<ManagedTypeName>,
+    /// `TypeAttributes` of this type.
+    pub flags: u32,
+    pub methods: Vec<MethodDefInfo>,
+}
+impl TypeDefInfo {
+    /// Checks if this type is public, and not nested in another type.
+    pub fn is_public(&self) -> bool {
+        self.flags & 0x7 == 0x1
+    }
+    pub fn is_interface(&self) -> bool {
+        self.flags & 0x20 != 0
+    }
+    pub fn is_value_type(&self) -> bool {
+        matches!(
+            self.extends.as_ref().map(|extends| extends.path.as_ref()),
+            Some("System.ValueType" | "System.Enum")
+        )
+    }
+}
+/// A method defined within a .NET assembly.
+#[derive(Clone, Debug)]
+pub struct MethodDefInfo {
+    pub name: IString,
+    /// `MethodAttributes` of this method.
+    pub flags: u16,
+    /// Names and types of the parameters, not including `this`.
+    pub params: Vec<(IString, SigType)>,
+    pub output: SigType,
+    /// Set if the method has generic parameters of its own.
+    pub is_generic: bool,
+}
+impl MethodDefInfo {
+    pub fn is_public(&self) -> bool {
+        self.flags & 0x7 == 0x6
+    }
+    pub fn is_static(&self) -> bool {
+        self.flags & 0x10 != 0
+    }
+    pub fn is_virtual(&self) -> bool {
+        self.flags & 0x40 != 0
+    }
+    /// Checks if the name of this method has a special meaning(eg. it is a constructor, or a property getter).
+    pub fn is_special_name(&self) -> bool {
+        self.flags & 0x800 != 0
+    }
+}
+/// Reads a compressed unsigned intieger at `pos` within a signature, and moves `pos` past it.
+fn read_compressed(sig: &[u8], pos: &mut usize) -> Result<u32, MetadataError> {
+    let byte = |offset: usize| {
+        sig.get(*pos + offset)
+            .map(|byte| u32::from(*byte))
+            .ok_or(MetadataError::Malformed("signature too short"))
+    };
+    let first = byte(0)?;
+    let (value, len) = if first & 0x80 == 0 {
+        (first, 1)
+    } else if first & 0xC0 == 0x80 {
+        (((first & 0x3F) << 8) | byte(1)?, 2)
+    } else if first & 0xE0 == 0xC0 {
+        (
+            ((first & 0x1F) << 24) | (byte(1)? << 16) | (byte(2)? << 8) | byte(3)?,
+            4,
+        )
+    } else {
+        return Err(MetadataError::Malformed("bad compressed intieger"));
+    };
+    *pos += len;
+    Ok(value)
+}
+/// Computes the token of public key `public_key`: the last 8 bytes of its SHA-1 hash, in reverse order.
+pub fn public_key_token(public_key: &[u8]) -> [u8; 8] {
+    let hash = sha1(public_key);
+    let mut token = [0; 8];
+    for (token_byte, hash_byte) in token.iter_mut().zip(hash.iter().rev()) {
+        *token_byte = *hash_byte;
+    }
+    token
+}
+/// Computes the SHA-1 hash of `data`. SHA-1 is not used for anything security related here, only to compute public key tokens.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [
+        0x6745_2301,
+        0xEFCD_AB89,
+        0x98BA_DCFE,
+        0x1032_5476,
+        0xC3D2_E1F0,
+    ];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    for chunk in message.chunks_exact(64) {
+        let mut words = [0u32; 80];
+        for (i, word) in chunk.chunks_exact(4).enumerate() {
+            words[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ED9_EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (state, value) in state.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+    let mut hash = [0; 20];
+    for (bytes, word) in hash.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    hash
+}
+#[test]
+fn sha1_hash() {
+    assert_eq!(
+        sha1(b"abc"),
+        [
+            0xA9, 0x99, 0x3E, 0x36, 0x47, 0x06, 0x81, 0x6A, 0xBA, 0x3E, 0x25, 0x71, 0x78, 0x50,
+            0xC2, 0x6C, 0x9C, 0xD0, 0xD8, 0x9D
+        ]
+    );
+}
+#[test]
+fn ecma_public_key_token() {
+    // The "ECMA key", used by framework assemblies like `mscorlib`.
+    let ecma_key = [0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(
+        public_key_token(&ecma_key),
+        [0xB7, 0x7A, 0x5C, 0x56, 0x19, 0x34, 0xE0, 0x89]
+    );
+}
+#[test]
+fn compressed_intiegers() {
+    let sig = [0x03, 0x80, 0x80, 0xC0, 0x00, 0x40, 0x00];
+    let mut pos = 0;
+    assert_eq!(read_compressed(&sig, &mut pos).unwrap(), 0x03);
+    assert_eq!(read_compressed(&sig, &mut pos).unwrap(), 0x80);
+    assert_eq!(read_compressed(&sig, &mut pos).unwrap(), 0x4000);
+    assert_eq!(pos, sig.len());
+}
+#[test]
+fn truncated_metadata() {
+    // Metadata root with a single, empty `#Blob` stream.
+    let mut root = vec![0x42, 0x53, 0x4A, 0x42, 1, 0, 1, 0, 0, 0, 0, 0, 4, 0, 0, 0];
+    root.extend(b"v4\0\0");
+    root.extend([0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    root.extend(b"#Blob\0\0\0");
+    assert!(matches!(
+        Metadata::from_metadata_root(&root),
+        Err(MetadataError::Malformed("no table stream"))
+    ));
+    for len in 0..root.len() {
+        assert!(matches!(
+            Metadata::from_metadata_root(&root[..len]),
+            Err(MetadataError::Malformed(_))
+        ));
+    }
+    // Blobs with a 2 byte and a 4 byte length, cut off within the length.
+    for blobs in [&[0x80][..], &[0xC0, 0, 0][..]] {
+        let metadata = Metadata {
+            strings: &[],
+            blobs,
+            tables: &[],
+            row_counts: [0; TABLE_COUNT],
+            table_offsets: [0; TABLE_COUNT],
+            wide_strings: false,
+            wide_guids: false,
+            wide_blobs: false,
+        };
+        assert!(matches!(
+            metadata.blob(0),
+            Err(MetadataError::Malformed("bad blob length"))
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    access_modifier::AccessModifer,
+    assembly::Assembly,
+    cil_op::{CILOp, CallSite},
+    function_sig::FnSig,
+    method::Method,
+    r#type::{DotnetTypeRef, Type},
+    type_def::TypeDef,
+};
+use rustc_hir::def::DefKind;
+use rustc_hir::definitions::DefPathData;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::{Instance, InstanceDef, ParamEnv, TyCtxt, TyKind};
+use rustc_session::config::CrateType;
+use rustc_span::def_id::{DefId, LOCAL_CRATE};
+/// Checks if the crate is built as a library meant to be used by other .NET code.
+pub fn is_exporting_library(tcx: TyCtxt) -> bool {
+    tcx.crate_types()
+        .iter()
+        .any(|crate_type| matches!(crate_type, CrateType::Cdylib | CrateType::Dylib))
+}
+/// For libraries, exposes all `pub` functions of the root crate as public static methods. The methods are placed in a class named after the crate,
+/// in a namespace derived from the module path of the function(`my_lib::math::add` becomes `my_lib.math.MyLib::add`).
+/// `pub` inherent methods of local types become methods of the .NET type representing them.
+pub fn add_exports<'tcx>(
+    asm: &mut Assembly,
+    tcx: TyCtxt<'tcx>,
+    items: impl Iterator<Item = &'tcx MonoItem<'tcx>>,
+) {
+    if !is_exporting_library(tcx) {
+        return;
+    }
+    let items: Vec<_> = items.collect();
+    add_type_exports(asm, tcx, &items);
+    let class_name = pascal_case(tcx.crate_name(LOCAL_CRATE).as_str());
+    let mut exports: Vec<(String, Method)> = items
+        .into_iter()
+        .filter_map(|item| match item {
+            MonoItem::Fn(instance) if is_exported(tcx, instance) => Some((item, instance)),
+            _ => None,
+        })
+        .map(|(item, instance)| {
+            let def_id = instance.def_id();
+            let namespace = module_path(tcx, def_id).join(".");
+            let sig = FnSig::from_poly_sig(
+                &instance.ty(tcx, ParamEnv::reveal_all()).fn_sig(tcx),
+                tcx,
+                instance,
+            )
+            .expect("Could not get the signature of an exported function!");
+            let symbol = crate::utilis::function_name(item.symbol_name(tcx));
+            let wrapper = export_wrapper(tcx.item_name(def_id).as_str(), &symbol, sig);
+            (format!("{namespace}.{class_name}"), wrapper)
+        })
+        .collect();
+    // Mono items are not ordered, so exports are sorted to keep the output deterministic.
+    exports.sort_by(|(a_class, a), (b_class, b)| (a_class, a.name()).cmp(&(b_class, b.name())));
+    let mut class: Option<TypeDef> = None;
+    for (class_name, wrapper) in exports {
+        if class.as_ref().map(TypeDef::name) != Some(class_name.as_str()) {
+            if let Some(class) = class.take() {
+                asm.add_typedef(class);
+            }
+            class = Some(export_class(&class_name));
+        }
+        class.as_mut().unwrap().add_method(wrapper);
+    }
+    if let Some(class) = class {
+        asm.add_typedef(class);
+    }
+}
+/// Adds `pub` inherent methods of non-generic local types to the types they belong to. Methods taking `self` become instance methods,
+/// and `&self` accessors named after a field(`fn x(&self) -> f32`) become read-only properties(`X`).
+fn add_type_exports<'tcx>(asm: &mut Assembly, tcx: TyCtxt<'tcx>, items: &[&'tcx MonoItem<'tcx>]) {
+    let mut exports: Vec<(String, Method, Option<(String, Type)>)> = items
+        .iter()
+        .filter_map(|item| match item {
+            MonoItem::Fn(instance) if is_exported_method(tcx, instance) => Some((item, instance)),
+            _ => None,
+        })
+        .filter_map(|(item, instance)| {
+            let def_id = instance.def_id();
+            let self_ty = tcx.type_of(tcx.parent(def_id)).instantiate_identity();
+            let TyKind::Adt(adt_def, args) = self_ty.kind() else {
+                return None;
+            };
+            if !adt_def.did().is_local() || !args.is_empty() {
+                return None;
+            }
+            let type_name = crate::utilis::adt_name(adt_def);
+            let poly_sig = instance.ty(tcx, ParamEnv::reveal_all()).fn_sig(tcx);
+            let sig = FnSig::from_poly_sig(&poly_sig, tcx, instance)
+                .expect("Could not get the signature of an exported method!");
+            let symbol = crate::utilis::function_name(item.symbol_name(tcx));
+            let name = tcx.item_name(def_id).to_string();
+            if !tcx.associated_item(def_id).fn_has_self_parameter {
+                let wrapper = export_wrapper(&pascal_case(&name), &symbol, sig);
+                return Some((type_name.to_string(), wrapper, None));
+            }
+            let self_kind = match poly_sig.skip_binder().inputs()[0].kind() {
+                TyKind::Ref(_, _, mutability) => SelfKind::Ref(mutability.is_mut()),
+                _ => SelfKind::Value,
+            };
+            let self_tpe = Type::DotnetType(Box::new(DotnetTypeRef::new(None, &type_name)));
+            // Only accessors named after a field are turned into properties, other getter-like methods(eg. `len`) stay methods.
+            let is_getter = matches!(self_kind, SelfKind::Ref(false))
+                && sig.inputs().len() == 1
+                && *sig.output() != Type::Void
+                && adt_def
+                    .all_fields()
+                    .any(|field| field.name.as_str() == name);
+            if is_getter {
+                let property = pascal_case(&name);
+                let tpe = sig.output().clone();
+                let getter = instance_wrapper(
+                    &format!("get_{property}"),
+                    &symbol,
+                    sig,
+                    self_kind,
+                    self_tpe,
+                );
+                Some((type_name.to_string(), getter, Some((property, tpe))))
+            } else {
+                let wrapper =
+                    instance_wrapper(&pascal_case(&name), &symbol, sig, self_kind, self_tpe);
+                Some((type_name.to_string(), wrapper, None))
+            }
+        })
+        .collect();
+    exports.sort_by(|(a_type, a, _), (b_type, b, _)| (a_type, a.name()).cmp(&(b_type, b.name())));
+    for (type_name, method, property) in exports {
+        let Some(mut type_def) = asm.take_typedef(&type_name) else {
+            continue;
+        };
+        if let Some((property, tpe)) = property {
+            type_def.add_property(property.into(), tpe, method.name().into());
+        }
+        type_def.add_method(method);
+        asm.add_typedef(type_def);
+    }
+}
+/// Describes how a method receives `self`.
+#[derive(Clone, Copy)]
+pub(crate) enum SelfKind {
+    /// `&self` or `&mut self`(if the bool is true).
+    Ref(bool),
+    /// `self`
+    Value,
+}
+/// Checks if function `instance` should be exported: it must be a non-generic, `pub` function, declared directly within a module of the local crate.
+fn is_exported<'tcx>(tcx: TyCtxt<'tcx>, instance: &Instance<'tcx>) -> bool {
+    let def_id = instance.def_id();
+    is_local_export(tcx, instance)
+        && tcx.def_kind(def_id) == DefKind::Fn
+        && tcx.def_kind(tcx.parent(def_id)) == DefKind::Mod
+}
+/// Checks if function `instance` is a non-generic, `pub` method, declared in an inherent `impl` block of the local crate.
+fn is_exported_method<'tcx>(tcx: TyCtxt<'tcx>, instance: &Instance<'tcx>) -> bool {
+    let def_id = instance.def_id();
+    is_local_export(tcx, instance)
+        && tcx.def_kind(def_id) == DefKind::AssocFn
+        && tcx.def_kind(tcx.parent(def_id)) == DefKind::Impl { of_trait: false }
+}
+/// Checks if `instance` is a non-generic item of the local crate, which is visible from outside of it.
+fn is_local_export<'tcx>(tcx: TyCtxt<'tcx>, instance: &Instance<'tcx>) -> bool {
+    matches!(instance.def, InstanceDef::Item(_))
+        && instance.args.is_empty()
+        && instance
+            .def_id()
+            .as_local()
+            .is_some_and(|local| tcx.effective_visibilities(()).is_exported(local))
+}
+/// Returns the crate name followed by names of all the modules containing item `def_id`.
+fn module_path(tcx: TyCtxt, def_id: DefId) -> Vec<String> {
+    let mut path = vec![tcx.crate_name(LOCAL_CRATE).to_string()];
+    path.extend(tcx.def_path(tcx.parent(def_id)).data.iter().filter_map(
+        |segment| match segment.data {
+            DefPathData::TypeNs(name) => Some(name.to_string()),
+            _ => None,
+        },
+    ));
+    path
+}
+/// Creates a static class, which will contain exported methods.
+fn export_class(name: &str) -> TypeDef {
+    let mut class = TypeDef::nameonly(name);
+    class.set_extends(DotnetTypeRef::new(Some("System.Runtime"), "System.Object"));
+    class.set_static();
+    class
+}
+/// Creates a public static method named `name`, which forwards all its arguments to the method `symbol`.
+fn export_wrapper(name: &str, symbol: &str, sig: FnSig) -> Method {
+    let mut ops: Vec<CILOp> = (0..sig.inputs().len() as u32).map(CILOp::LDArg).collect();
+    ops.push(CILOp::Call(CallSite::boxed(
+        None,
+        symbol.into(),
+        sig.clone(),
+        true,
+    )));
+    ops.push(CILOp::Ret);
+    let mut method = Method::new(AccessModifer::Public, true, sig, name, vec![]);
+    method.set_ops(ops);
+    method
+}
+/// Creates a public instance method named `name`, which passes `this` and all the other arguments to the method `symbol`. A `this`
+/// passed by reference is pinned for the duration of the call, since it may point into an object the GC could move.
+pub(crate) fn instance_wrapper(
+    name: &str,
+    symbol: &str,
+    sig: FnSig,
+    self_kind: SelfKind,
+    self_tpe: Type,
+) -> Method {
+    // `this` is a managed reference to the value type, while Rust code expects a pointer or the value itself.
+    let (mut ops, locals) = match self_kind {
+        SelfKind::Ref(_) => (
+            vec![
+                CILOp::LDArg(0),
+                CILOp::STLoc(0),
+                CILOp::LDLoc(0),
+                CILOp::ConvUSize(false),
+            ],
+            vec![Type::PinnedRef(Box::new(self_tpe))],
+        ),
+        SelfKind::Value => (
+            vec![CILOp::LDArg(0), CILOp::LdObj(Box::new(self_tpe))],
+            vec![],
+        ),
+    };
+    ops.extend((1..sig.inputs().len() as u32).map(CILOp::LDArg));
+    ops.push(CILOp::Call(CallSite::boxed(
+        None,
+        symbol.into(),
+        sig.clone(),
+        true,
+    )));
+    ops.push(CILOp::Ret);
+    let mut method = Method::new(AccessModifer::Public, false, sig, name, locals);
+    method.set_ops(ops);
+    method
+}
+/// Converts a snake_case name into PascalCase, which is the naming convention of .NET types.
+pub(crate) fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+#[test]
+fn pascal_case_names() {
+    assert_eq!(pascal_case("my_lib"), "MyLib");
+    assert_eq!(pascal_case("fastrand"), "Fastrand");
+}
+#[test]
+fn export_class_il() {
+    use crate::assembly_exporter::{ilasm_exporter::ILASMExporter, AssemblyExporter, AssemblyInfo};
+    let sig = FnSig::new(&[Type::I32, Type::I32], &Type::I32);
+    // Like all functions added by `Assembly::add_fn`, the function itself is private.
+    let exported = Method::new(
+        AccessModifer::Private,
+        true,
+        sig.clone(),
+        "_ZN6my_lib4math3add",
+        vec![],
+    );
+    let mut class = export_class("my_lib.math.MyLib");
+    class.add_method(export_wrapper("add", "_ZN6my_lib4math3add", sig));
+    let mut exporter = ILASMExporter::init(&AssemblyInfo::default());
+    exporter.add_type(&class);
+    exporter.add_method(&exported);
+    let cil = exporter.cil();
+    assert!(cil.contains(
+        ".class public abstract sealed my_lib.math.MyLib extends [System.Runtime]System.Object{"
+    ));
+    assert!(cil.contains(".method public hidebysig static int32 'add'(int32,int32){"));
+    assert!(cil.contains("call  int32  '_ZN6my_lib4math3add'(int32,int32)"));
+    assert!(
+        cil.contains(".method private hidebysig static int32 '_ZN6my_lib4math3add'(int32,int32){")
+    );
+}
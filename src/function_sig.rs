@@ -1,7 +1,7 @@
 use crate::{
     codegen_error::MethodCodegenError, r#type::Type, utilis::skip_binder_if_no_generic_types,
 };
-use rustc_middle::ty::{Instance, PolyFnSig, TyCtxt};
+use rustc_middle::ty::{Instance, PolyFnSig, Ty, TyCtxt};
 use serde::{Deserialize, Serialize};
 /// Function signature.
 #[derive(Clone, PartialEq, Serialize, Deserialize, Eq, Hash, Debug)]
@@ -54,6 +54,27 @@ impl FnSig {
         );
         Ok(Self { inputs, output })
     }
+    /// Creates the signature of a managed method, called using an interop intrinsic with signature `sig`. Arguments and return values
+    /// wrapped in `RustcCLRInteropManagedGenericArg` are declared as generic parameters of the class owning the method.
+    pub fn managed_from_poly_sig<'tcx>(
+        sig: &PolyFnSig<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        method: &Instance<'tcx>,
+    ) -> Result<Self, MethodCodegenError> {
+        let managed_type = |ty: Ty<'tcx>| {
+            crate::r#type::managed_sig_type(
+                crate::utilis::monomorphize(method, ty, tcx),
+                tcx,
+                method,
+            )
+        };
+        let inputs = skip_binder_if_no_generic_types(sig.inputs())?
+            .iter()
+            .map(|input| managed_type(*input))
+            .collect();
+        let output = managed_type(skip_binder_if_no_generic_types(sig.output())?);
+        Ok(Self { inputs, output })
+    }
     pub fn inputs(&self) -> &[Type] {
         &self.inputs
     }
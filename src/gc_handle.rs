@@ -0,0 +1,154 @@
+use crate::{
+    cil_op::{CILOp, CallSite},
+    function_sig::FnSig,
+    r#type::{DotnetTypeRef, Type},
+};
+use rustc_middle::ty::{Instance, Ty, TyCtxt, TyKind};
+/// Checks if `ty` is a reference to a managed object(`RustcCLRInteropManagedClass`, `RustcCLRInteropManagedArray` or
+/// `RustcCLRInteropManagedDelegate`). Such references are invisible to the GC when placed in unmanaged memory, so they
+/// can only be stored there wrapped in a `GCHandle`.
+pub fn is_managed_ref(ty: Ty) -> bool {
+    match ty.kind() {
+        TyKind::Adt(adt_def, _) => {
+            let name = crate::utilis::adt_name(adt_def);
+            matches!(
+                name.rsplit('.').next(),
+                Some(
+                    crate::r#type::INTEROP_CLASS_TPE_NAME
+                        | crate::r#type::INTEROP_ARR_TPE_NAME
+                        | crate::r#type::INTEROP_DELEGATE_TPE_NAME
+                )
+            )
+        }
+        _ => false,
+    }
+}
+/// Checks if a value of type `ty` is, or directly contains(as a field of a struct, tuple or array), a managed reference.
+/// Managed references behind pointers are not checked, since the pointer itself can be safely stored anywhere.
+pub fn contains_managed_ref<'tcx>(
+    ty: Ty<'tcx>,
+    tcx: TyCtxt<'tcx>,
+    method_instance: &Instance<'tcx>,
+) -> bool {
+    let ty = crate::utilis::monomorphize(method_instance, ty, tcx);
+    if is_managed_ref(ty) {
+        return true;
+    }
+    match ty.kind() {
+        TyKind::Adt(adt_def, subst) => adt_def
+            .all_fields()
+            .any(|field| contains_managed_ref(field.ty(tcx, subst), tcx, method_instance)),
+        TyKind::Tuple(elements) => elements
+            .iter()
+            .any(|element| contains_managed_ref(element, tcx, method_instance)),
+        TyKind::Array(element, _) => contains_managed_ref(*element, tcx, method_instance),
+        _ => false,
+    }
+}
+/// Checks that a value of type `pointed` can be written trough a pointer of type `ptr`. Rust references to managed references
+/// are byrefs to GC-tracked places(locals or fields), but raw pointers may point to unmanaged memory, so writing managed
+/// references trough them is rejected: such references must be wrapped in mycorrhiza's `GCHandle<T>` instead.
+pub fn check_ptr_store<'tcx>(
+    ptr: Ty<'tcx>,
+    pointed: Ty<'tcx>,
+    tcx: TyCtxt<'tcx>,
+    method_instance: &Instance<'tcx>,
+) {
+    if matches!(ptr.kind(), TyKind::RawPtr(_))
+        && contains_managed_ref(pointed, tcx, method_instance)
+    {
+        panic!(
+            "Can't store {pointed:?}, which contains a managed reference, trough the raw pointer {ptr:?}, which may point to \
+unmanaged memory invisible to the GC. Wrap the managed reference in `mycorrhiza::system::runtime::interop_services::GCHandle` instead."
+        );
+    }
+}
+/// The `System.Runtime.InteropServices.GCHandle` value type.
+fn gc_handle() -> DotnetTypeRef {
+    let mut tpe = DotnetTypeRef::new(
+        Some("System.Runtime"),
+        "System.Runtime.InteropServices.GCHandle",
+    );
+    tpe.set_valuetype(true);
+    tpe
+}
+fn object() -> Type {
+    DotnetTypeRef::new(Some("System.Runtime"), "System.Object").into()
+}
+/// Ops turning an object reference on top of the stack into a `GCHandle` to it, converted to a native int.
+pub fn alloc_handle() -> Vec<CILOp> {
+    let handle: Type = gc_handle().into();
+    vec![
+        CILOp::Call(CallSite::boxed(
+            Some(gc_handle()),
+            "Alloc".into(),
+            FnSig::new(&[object()], &handle),
+            true,
+        )),
+        CILOp::Call(CallSite::boxed(
+            Some(gc_handle()),
+            "ToIntPtr".into(),
+            FnSig::new(&[handle], &Type::ISize),
+            true,
+        )),
+    ]
+}
+/// Ops turning an object reference on top of the stack into a pinned `GCHandle` to it, converted to a native int. The object
+/// will not be moved by the GC until the handle is freed.
+pub fn alloc_pinned_handle() -> Vec<CILOp> {
+    let handle: Type = gc_handle().into();
+    let mut handle_kind = DotnetTypeRef::new(
+        Some("System.Runtime"),
+        "System.Runtime.InteropServices.GCHandleType",
+    );
+    handle_kind.set_valuetype(true);
+    vec![
+        // GCHandleType.Pinned
+        CILOp::LdcI32(3),
+        CILOp::Call(CallSite::boxed(
+            Some(gc_handle()),
+            "Alloc".into(),
+            FnSig::new(&[object(), handle_kind.into()], &handle),
+            true,
+        )),
+        CILOp::Call(CallSite::boxed(
+            Some(gc_handle()),
+            "ToIntPtr".into(),
+            FnSig::new(&[handle], &Type::ISize),
+            true,
+        )),
+    ]
+}
+/// Ops converting the native int on top of the stack back into a `GCHandle`, and calling its instance method `name`.
+fn call_on_handle(name: &str, output: Type) -> Vec<CILOp> {
+    let handle: Type = gc_handle().into();
+    vec![
+        CILOp::Call(CallSite::boxed(
+            Some(gc_handle()),
+            "FromIntPtr".into(),
+            FnSig::new(&[Type::ISize], &handle),
+            true,
+        )),
+        // Instance methods of value types need the adress of `this`.
+        CILOp::NewTMPLocal(handle.clone().into()),
+        CILOp::SetTMPLocal,
+        CILOp::LoadAddresOfTMPLocal,
+        CILOp::Call(CallSite::boxed(
+            Some(gc_handle()),
+            name.into(),
+            FnSig::new(&[handle], &output),
+            false,
+        )),
+        CILOp::FreeTMPLocal,
+    ]
+}
+/// Ops replacing the handle on top of the stack with the object of type `target` it refers to.
+pub fn handle_target(target: Type) -> Vec<CILOp> {
+    let mut ops = call_on_handle("get_Target", object());
+    ops.push(CILOp::CastClass(target.into()));
+    ops
+}
+/// Ops freeing the handle on top of the stack.
+pub fn free_handle() -> Vec<CILOp> {
+    call_on_handle("Free", Type::Void)
+}
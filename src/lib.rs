@@ -35,6 +35,8 @@ pub mod assembly;
 /// Module containg ILASM-based exporter and code shared between all IL exporter.
 pub mod assembly_exporter;
 mod basic_block;
+/// Generation of mycorrhiza wrappers around managed types, from the metadata of assemblies defining them.
+pub mod bindgen;
 /// Code handling binary operations
 mod binop;
 
@@ -47,22 +49,36 @@ mod cil_op;
 mod codegen_error;
 /// Test harnesses.
 mod compile_test;
+/// Exposing public functions of libraries as methods callable from other .NET code.
+mod exports;
 /// Code handling loading constant values in CIL.
 mod constant;
+/// Generation of C# wrappers, which make Rust libraries easier to use from C#.
+pub mod csharp_facade;
+/// Removal of methods and types unreachable from the entrypoint or public items.
+mod dead_code;
+/// Reading identity and other metadata of external .NET assemblies.
+pub mod dotnet_metadata;
 /// Code detecting and inserting wrappers around entrypoints.
 mod entrypoint;
 /// Signature of a function (inputs)->output
 mod function_sig;
+/// Storing references to managed objects in unmanaged memory, using `GCHandle`s.
+mod gc_handle;
 /// Implementation of some libc functions in CIL assembly. Will likely be removed and mostly replaced by functions implmented using mycorrhize.
 pub mod libc;
 /// A representation of a .NET method
 mod method;
+/// Declarations of native functions called using P/Invoke.
+mod pinvoke;
 /// Handles a MIR operand.
 mod operand;
 /// Method-level CIL opitimizations
 mod opt;
 /// Code handling getting/setting/adressing memory locations.
 mod place;
+/// Generation of the files CoreCLR needs to run an executable(`.runtimeconfig.json`, `.deps.json`).
+pub mod runtime_config;
 /// Converts righthandside of a MIR statement into CIL ops.
 mod rvalue;
 /// Code dealing with truning an individual MIR statement into CIL ops.
@@ -77,6 +93,8 @@ mod r#type;
 mod type_def;
 /// Implementations of unary operations.
 mod unop;
+/// Rust trait impls implementing managed interfaces, or overriding virtual methods of `System.Object`.
+mod virtuals;
 /// Contains small helper functions(debug assertions, functions used to get field names, etc), which are frequently used, but are not specific to a part of the coodegen.
 mod utilis;
 // rustc functions used here.
@@ -132,6 +150,13 @@ impl CodegenBackend for MyBackend {
             }
         }
 
+        pinvoke::add_pinvoke_decls(&mut codegen, tcx);
+        exports::add_exports(&mut codegen, tcx, cgus.iter().flat_map(|cgu| cgu.items().keys()));
+        virtuals::add_virtuals(&mut codegen, tcx, cgus.iter().flat_map(|cgu| cgu.items().keys()));
+        codegen.add_typedef(type_def::rust_panic_type());
+        codegen.add_typedef(type_def::closure_env_type());
+        let crate_name = tcx.crate_name(rustc_span::def_id::LOCAL_CRATE);
+        codegen.set_info(assembly_exporter::AssemblyInfo::from_crate(crate_name.as_str()));
         if let Some((entrypoint, _kind)) = tcx.entry_fn(()) {
             let penv = rustc_middle::ty::ParamEnv::empty();
             let entrypoint = rustc_middle::ty::Instance::resolve(
@@ -219,12 +244,17 @@ impl ArchiveBuilderBuilder for RlibArchiveBuilder {
     fn create_dll_import_lib(
         &self,
         _sess: &Session,
-        _lib_name: &str,
+        lib_name: &str,
         _dll_imports: &[rustc_session::cstore::DllImport],
-        _tmpdir: &Path,
+        tmpdir: &Path,
         _is_direct_dependency: bool,
     ) -> PathBuf {
-        unimplemented!("creating dll imports is not supported");
+        // Imported functions are declared using P/Invoke, and resolved by the runtime, so the import library is empty.
+        let import_lib_path = tmpdir.join(format!("{lib_name}_import.lib"));
+        // An archive with no members consists of only the global header.
+        std::fs::write(&import_lib_path, b"!<arch>\n")
+            .expect("Could not create the dll import library!");
+        import_lib_path
     }
 }
 #[no_mangle]
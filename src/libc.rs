@@ -77,7 +77,10 @@ pub fn insert_libc(asm: &mut Assembly) {
     asm.add_typedef(crate::type_def::TypeDef::nameonly("Unresolved"));
     asm.add_typedef(crate::type_def::TypeDef::nameonly("RustVoid"));
     asm.add_typedef(crate::type_def::TypeDef::nameonly("Foreign"));
-    asm.add_typedef(crate::type_def::TypeDef::nameonly("RustStr"));
+    let mut rust_str = crate::type_def::TypeDef::nameonly("RustStr");
+    rust_str.add_field("_ptr".into(), Type::Ptr(Box::new(Type::U8)));
+    rust_str.add_field("_length".into(), Type::USize);
+    asm.add_typedef(rust_str);
     rust_slice(asm);
     math(asm);
     io(asm);
@@ -85,6 +88,8 @@ pub fn insert_libc(asm: &mut Assembly) {
     realloc(asm);
     free(asm);
     abort(asm);
+    // Emulated functions take precedence over native ones.
+    asm.remove_shadowed_pinvokes();
 }
 
 fn rust_slice(asm: &mut Assembly) {
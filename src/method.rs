@@ -11,6 +11,10 @@ pub struct Method {
     sig: FnSig,
     name: IString,
     locals: Vec<Type>,
+    /// Names of the locals, as they were in the Rust source. Locals past the end of this list, or set to [`None`], are unnamed.
+    local_names: Vec<Option<IString>>,
+    /// Names of the arguments(including `this`), as they were in the Rust source.
+    arg_names: Vec<Option<IString>>,
     ops: Vec<CILOp>,
     attributes: Vec<Attribute>,
 }
@@ -24,6 +28,12 @@ impl Hash for Method {
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum Attribute {
     EntryPoint,
+    /// The method has no body, and is imported from native library `library` using P/Invoke.
+    PInvoke {
+        library: IString,
+    },
+    /// The method is virtual. It overrides or implements the method of a base type or an interface with the same name and signature.
+    Virtual,
 }
 impl Method {
     pub fn new(
@@ -39,11 +49,17 @@ impl Method {
             sig,
             name: name.into(),
             locals,
+            local_names: Vec::new(),
+            arg_names: Vec::new(),
             ops: Vec::new(),
             attributes: Vec::new(),
         }
     }
     pub fn ensure_valid(&mut self) {
+        // P/Invoke declarations have no body.
+        if self.pinvoke_library().is_some() {
+            return;
+        }
         if let Some(CILOp::Ret) = self.ops.iter().last() {
             //Do nothing
         } else {
@@ -55,6 +71,18 @@ impl Method {
             .iter()
             .any(|attr| *attr == Attribute::EntryPoint)
     }
+    pub fn is_virtual(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attr| *attr == Attribute::Virtual)
+    }
+    /// Returns the name of the native library this method is imported from, if it is a P/Invoke declaration.
+    pub fn pinvoke_library(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|attr| match attr {
+            Attribute::PInvoke { library } => Some(library.as_ref()),
+            _ => None,
+        })
+    }
     pub fn explicit_inputs(&self) -> &[Type] {
         if self.is_static() {
             self.sig().inputs()
@@ -133,4 +161,18 @@ impl Method {
     pub fn set_locals(&mut self, locals: impl Into<Vec<Type>>) {
         self.locals = locals.into();
     }
+    /// Returns the name of local `local`, if it has one.
+    pub fn local_name(&self, local: usize) -> Option<&str> {
+        self.local_names.get(local)?.as_deref()
+    }
+    pub fn set_local_names(&mut self, local_names: impl Into<Vec<Option<IString>>>) {
+        self.local_names = local_names.into();
+    }
+    /// Returns the name of argument `arg`, if it has one. For instance methods, argument 0 is `this`.
+    pub fn arg_name(&self, arg: usize) -> Option<&str> {
+        self.arg_names.get(arg)?.as_deref()
+    }
+    pub fn set_arg_names(&mut self, arg_names: impl Into<Vec<Option<IString>>>) {
+        self.arg_names = arg_names.into();
+    }
 }
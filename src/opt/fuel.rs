@@ -0,0 +1,96 @@
+use crate::IString;
+/// Name of the enviroment variable used to set the amount of optimization fuel.
+const OPT_FUEL_ENV: &str = "OPT_FUEL";
+/// Name of the enviroment variable containing a comma-separated list of disabled optimization passes.
+const OPT_DISABLED_PASSES_ENV: &str = "OPT_DISABLED_PASSES";
+/// Limits the number of rewrites the optimizer is allowed to preform. Each individual rewrite consumes one unit of fuel, and once the fuel runs out,
+/// no more rewrites happen. This allows bisecting miscompilations down to the exact method and rewrite which caused them:
+/// if a program works with `OPT_FUEL=n` and breaks with `OPT_FUEL=n+1`, the rewrite reported when running with `OPT_FUEL=n+1` is the guilty one.
+#[derive(Debug)]
+pub struct OptFuel {
+    /// Remaining fuel. [`None`] means unlimited fuel.
+    remaining: Option<u32>,
+    /// Passes that should not run at all.
+    disabled_passes: Vec<IString>,
+    /// Name of the method currently being optimized, used when reporting which rewrite used up the last unit of fuel.
+    current_method: IString,
+}
+impl OptFuel {
+    /// Creates new optimization fuel. `remaining` of [`None`] means the fuel never runs out.
+    pub fn new(remaining: Option<u32>, disabled_passes: Vec<IString>) -> Self {
+        Self {
+            remaining,
+            disabled_passes,
+            current_method: "".into(),
+        }
+    }
+    /// Fuel that never runs out, with all passes enabled.
+    pub fn unlimited() -> Self {
+        Self::new(None, vec![])
+    }
+    /// Reads the fuel amount from `OPT_FUEL`, and the comma-separated list of disabled passes from `OPT_DISABLED_PASSES`.
+    /// Those enviroment variables are the supported way of configuring the optimizer: it runs within rustc, which has no flags
+    /// for passing options to codegen backends, and not in the linker, so linker arguments can't affect it.
+    pub fn from_env() -> Self {
+        let remaining = std::env::var(OPT_FUEL_ENV).ok().map(|fuel| {
+            fuel.trim().parse::<u32>().unwrap_or_else(|_| {
+                panic!("{OPT_FUEL_ENV} must be a non-negative intieger, but is {fuel:?}!")
+            })
+        });
+        let disabled_passes = std::env::var(OPT_DISABLED_PASSES_ENV)
+            .map(|passes| {
+                passes
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|pass| !pass.is_empty())
+                    .map(Into::into)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self::new(remaining, disabled_passes)
+    }
+    /// Sets the name of the method currently being optimized.
+    pub fn set_current_method(&mut self, name: &str) {
+        self.current_method = name.into();
+    }
+    /// Checks if pass `pass` is allowed to run. Passes are disabled either explicitly, or by running out of fuel.
+    pub fn pass_enabled(&self, pass: &str) -> bool {
+        self.remaining != Some(0)
+            && !self
+                .disabled_passes
+                .iter()
+                .any(|disabled| disabled.as_ref() == pass)
+    }
+    /// Tries to consume one unit of fuel for rewrite `rewrite` of pass `pass`. Returns `true` if the rewrite is allowed to happen.
+    pub fn consume(&mut self, pass: &str, rewrite: &str) -> bool {
+        match &mut self.remaining {
+            None => true,
+            Some(0) => false,
+            Some(remaining) => {
+                *remaining -= 1;
+                if *remaining == 0 {
+                    eprintln!(
+                        "Optimization fuel ran out. Last rewrite: {rewrite} in pass {pass}, method {method}.",
+                        method = self.current_method
+                    );
+                }
+                true
+            }
+        }
+    }
+}
+#[test]
+fn fuel_runs_out() {
+    let mut fuel = OptFuel::new(Some(2), vec![]);
+    assert!(fuel.pass_enabled("op2_combos"));
+    assert!(fuel.consume("op2_combos", "ldloc_stloc"));
+    assert!(fuel.consume("op2_combos", "ldloc_stloc"));
+    assert!(!fuel.consume("op2_combos", "ldloc_stloc"));
+    assert!(!fuel.pass_enabled("op2_combos"));
+}
+#[test]
+fn disabled_pass() {
+    let fuel = OptFuel::new(None, vec!["op4_combos".into()]);
+    assert!(fuel.pass_enabled("op2_combos"));
+    assert!(!fuel.pass_enabled("op4_combos"));
+}
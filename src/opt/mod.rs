@@ -1,36 +1,64 @@
 use crate::{cil_op::CILOp, method::Method, r#type::Type};
+/// Optimization fuel, used to bisect miscompilations caused by the optimizer.
+mod fuel;
+pub use fuel::OptFuel;
 const MAX_PASS: u32 = 8;
-pub fn opt_method(method: &mut Method) {
+pub fn opt_method(method: &mut Method, fuel: &mut OptFuel) {
     if !crate::OPTIMIZE_CIL {
         return;
     };
     //panic!("opt");
-    repalce_const_sizes(method.ops_mut());
+    fuel.set_current_method(method.name());
+    if fuel.pass_enabled("repalce_const_sizes") {
+        repalce_const_sizes(method.ops_mut(), fuel);
+    }
     for _ in 0..MAX_PASS {
-        op2_combos(method.ops_mut());
-        op3_combos(method.ops_mut());
-        op4_combos(method.ops_mut());
-        remove_zombie_sets(method.ops_mut());
+        if fuel.pass_enabled("op2_combos") {
+            op2_combos(method.ops_mut(), fuel);
+        }
+        if fuel.pass_enabled("op3_combos") {
+            op3_combos(method.ops_mut(), fuel);
+        }
+        if fuel.pass_enabled("op4_combos") {
+            op4_combos(method.ops_mut(), fuel);
+        }
+        if fuel.pass_enabled("remove_zombie_sets") {
+            remove_zombie_sets(method.ops_mut(), fuel);
+        }
         method.ops_mut().retain(|op| *op != CILOp::Nop);
-        remove_unused_locals(method);
+        if fuel.pass_enabled("remove_unused_locals") {
+            remove_unused_locals(method, fuel);
+        }
     }
 }
-fn repalce_const_sizes(ops: &mut [CILOp]) {
+fn repalce_const_sizes(ops: &mut [CILOp], fuel: &mut OptFuel) {
     ops.iter_mut().for_each(|op| match op {
         CILOp::SizeOf(tpe) => match tpe.as_ref() {
-            Type::U8 | Type::I8 => *op = CILOp::LdcI32(1),
+            Type::U8 | Type::I8 => {
+                if fuel.consume("repalce_const_sizes", "sizeof_byte") {
+                    *op = CILOp::LdcI32(1)
+                }
+            }
             _ => (),
         },
         _ => (),
     })
 }
-fn remove_unused_locals(method: &mut Method) {
+fn remove_unused_locals(method: &mut Method, fuel: &mut OptFuel) {
+    // Removing locals is a single rewrite of the whole method, so it only consumes fuel if there is something to remove.
+    let has_unused =
+        (0..method.locals().len()).any(|local| is_local_unused(method.get_ops(), local as u32));
+    if !has_unused || !fuel.consume("remove_unused_locals", "remove_locals") {
+        return;
+    }
     let mut local_map = vec![u32::MAX; method.locals().len()];
     let mut new_locals = Vec::with_capacity(method.locals().len());
+    let mut new_names = Vec::with_capacity(method.locals().len());
     for (local, tpe) in method.locals().iter().enumerate() {
         if local_map[local] == u32::MAX && !is_local_unused(method.get_ops(), local as u32) {
             local_map[local] = new_locals.len() as u32;
             new_locals.push(tpe.clone());
+            new_names.push(method.local_name(local).map(Into::into));
         }
     }
     method.ops_mut().iter_mut().for_each(|op| match op {
@@ -49,17 +77,20 @@ fn remove_unused_locals(method: &mut Method) {
         _ => (),
     });
     method.set_locals(new_locals);
+    method.set_local_names(new_names);
 }
-fn remove_zombie_sets(ops: &mut Vec<CILOp>) {
+fn remove_zombie_sets(ops: &mut Vec<CILOp>, fuel: &mut OptFuel) {
     for idx in 0..ops.len() {
         match ops[idx] {
             CILOp::STLoc(loc) => {
-                if is_local_dead(ops, loc) {
+                if is_local_dead(ops, loc) && fuel.consume("remove_zombie_sets", "dead_stloc") {
                     ops[idx] = CILOp::Pop;
                 }
             }
             CILOp::Label(label) => {
-                if is_label_unsused(ops, label) {
+                if is_label_unsused(ops, label)
+                    && fuel.consume("remove_zombie_sets", "unused_label")
+                {
                     ops[idx] = CILOp::Nop;
                 }
             }
@@ -67,7 +98,7 @@ fn remove_zombie_sets(ops: &mut Vec<CILOp>) {
         }
     }
 }
-fn op2_combos(ops: &mut Vec<CILOp>) {
+fn op2_combos(ops: &mut Vec<CILOp>, fuel: &mut OptFuel) {
     if ops.is_empty() {
         return;
     }
@@ -75,39 +106,45 @@ fn op2_combos(ops: &mut Vec<CILOp>) {
         let (op1, op2) = (&ops[idx], &ops[idx + 1]);
         match (op1, op2) {
             (CILOp::LDLoc(a), CILOp::STLoc(b)) => {
-                if a == b {
+                if a == b && fuel.consume("op2_combos", "ldloc_stloc") {
                     ops[idx] = CILOp::Nop;
                     ops[idx + 1] = CILOp::Nop;
                 }
             }
             (CILOp::STLoc(a), CILOp::LDLoc(b)) => {
-                if a == b {
+                if a == b && fuel.consume("op2_combos", "stloc_ldloc") {
                     ops[idx + 1] = CILOp::STLoc(*a);
                     ops[idx] = CILOp::Dup;
                 }
             }
             (CILOp::Dup | CILOp::LDLoc(_) | CILOp::LDLocA(_), CILOp::Pop) => {
-                ops[idx] = CILOp::Nop;
-                ops[idx + 1] = CILOp::Nop;
+                if fuel.consume("op2_combos", "load_pop") {
+                    ops[idx] = CILOp::Nop;
+                    ops[idx + 1] = CILOp::Nop;
+                }
             }
             (CILOp::GoTo(target), CILOp::Label(label)) => {
-                if target == label {
+                if target == label && fuel.consume("op2_combos", "goto_next") {
                     ops[idx] = CILOp::Nop;
                 }
             }
             (CILOp::LdcI32(0) | CILOp::LdcI64(0), CILOp::BEq(target)) => {
-                ops[idx + 1] = CILOp::BZero(*target);
-                ops[idx] = CILOp::Nop;
+                if fuel.consume("op2_combos", "beq_zero") {
+                    ops[idx + 1] = CILOp::BZero(*target);
+                    ops[idx] = CILOp::Nop;
+                }
             }
             (CILOp::Lt, CILOp::BZero(target)) => {
-                ops[idx + 1] = CILOp::BGe(*target);
-                ops[idx] = CILOp::Nop;
+                if fuel.consume("op2_combos", "lt_bzero") {
+                    ops[idx + 1] = CILOp::BGe(*target);
+                    ops[idx] = CILOp::Nop;
+                }
             }
             _ => (),
         }
     }
 }
-fn op3_combos(ops: &mut Vec<CILOp>) {
+fn op3_combos(ops: &mut Vec<CILOp>, fuel: &mut OptFuel) {
     if ops.len() < 3 {
         return;
     }
@@ -115,17 +152,21 @@ fn op3_combos(ops: &mut Vec<CILOp>) {
         let (op1, op2, op3) = (&ops[idx], &ops[idx + 1], &ops[idx + 2]);
         match (op1, op2, op3) {
             (CILOp::LdcI32(1), CILOp::LDLoc(_) | CILOp::LDArg(_), CILOp::Mul) => {
-                ops[idx] = op2.clone();
-                ops[idx + 1] = CILOp::Nop;
-                ops[idx + 2] = CILOp::Nop;
+                if fuel.consume("op3_combos", "mul_one_lhs") {
+                    ops[idx] = op2.clone();
+                    ops[idx + 1] = CILOp::Nop;
+                    ops[idx + 2] = CILOp::Nop;
+                }
             }
             (_, CILOp::LdcI32(1), CILOp::Mul) => {
-                ops[idx] = op1.clone();
-                ops[idx + 1] = CILOp::Nop;
-                ops[idx + 2] = CILOp::Nop;
+                if fuel.consume("op3_combos", "mul_one_rhs") {
+                    ops[idx] = op1.clone();
+                    ops[idx + 1] = CILOp::Nop;
+                    ops[idx + 2] = CILOp::Nop;
+                }
             }
             (CILOp::BEq(a1), CILOp::GoTo(b), CILOp::Label(a2)) => {
-                if a1 == a2 {
+                if a1 == a2 && fuel.consume("op3_combos", "beq_goto_label") {
                     let a = *a1;
                     let b = *b;
                     ops[idx] = CILOp::BNe(b);
@@ -134,7 +175,7 @@ fn op3_combos(ops: &mut Vec<CILOp>) {
                 }
             }
             (CILOp::GoTo(a1), CILOp::Label(b), CILOp::Label(a2)) => {
-                if a1 == a2 {
+                if a1 == a2 && fuel.consume("op3_combos", "goto_label_label") {
                     let a = *a1;
                     let b = *b;
                     ops[idx] = CILOp::Nop;
@@ -146,7 +187,7 @@ fn op3_combos(ops: &mut Vec<CILOp>) {
         }
     }
 }
-fn op4_combos(ops: &mut [CILOp]) {
+fn op4_combos(ops: &mut [CILOp], fuel: &mut OptFuel) {
     if ops.len() < 4 {
         return;
     }
@@ -160,7 +201,7 @@ fn op4_combos(ops: &mut [CILOp]) {
                 CILOp::LDLoc(b2),
                 CILOp::Add | CILOp::Mul,
             ) => {
-                if b1 == b2 {
+                if b1 == b2 && fuel.consume("op4_combos", "commutative_reorder") {
                     let op2 = op2.clone();
                     ops[idx + 1] = op4.clone();
                     ops[idx] = op2;
@@ -197,7 +238,7 @@ fn op4_combos(ops: &mut [CILOp]) {
                 CILOp::LDLoc(c) | CILOp::LDArg(c),
                 CILOp::LDLoc(b2),
             ) => {
-                if b1 == b2 && c != b1 {
+                if b1 == b2 && c != b1 && fuel.consume("op4_combos", "stloc_ldloc_reorder") {
                     let op1 = op1.clone();
                     let op3 = op3.clone();
                     let b = *b1;
@@ -232,11 +273,14 @@ fn is_local_unused(ops: &[CILOp], local: u32) -> bool {
 fn is_label_unsused(ops: &[CILOp], label: u32) -> bool {
     !ops.iter().any(|op| match op {
         CILOp::GoTo(target) => label == *target,
+        CILOp::Leave(target) => label == *target,
         CILOp::BEq(target) => label == *target,
         CILOp::BNe(target) => label == *target,
         CILOp::BLt(target) => label == *target,
         CILOp::BGe(target) => label == *target,
+        CILOp::BLtUn(target) => label == *target,
         CILOp::BZero(target) => label == *target,
+        CILOp::Switch(targets) => targets.contains(&label),
         _ => false,
     })
 }
@@ -248,10 +292,23 @@ fn cond_reordering() {
         CILOp::LDLoc(0),
         CILOp::BGe(0),
     ];
-    op4_combos(&mut ops);
+    op4_combos(&mut ops, &mut OptFuel::unlimited());
     assert_eq!(
         ops,
         [CILOp::Dup, CILOp::STLoc(0), CILOp::LDLoc(1), CILOp::BLt(0)]
     );
     //panic!("ops:{ops:?}")
 }
+#[test]
+fn no_fuel_no_rewrites() {
+    let mut ops = vec![CILOp::LDLoc(0), CILOp::STLoc(0), CILOp::Dup, CILOp::Pop];
+    let original = ops.clone();
+    op2_combos(&mut ops, &mut OptFuel::new(Some(0), vec![]));
+    assert_eq!(ops, original);
+    op2_combos(&mut ops, &mut OptFuel::new(Some(1), vec![]));
+    assert_eq!(
+        ops,
+        [CILOp::Nop, CILOp::Nop, CILOp::Dup, CILOp::Pop],
+        "Only the first rewrite should have been preformed."
+    );
+}
@@ -0,0 +1,45 @@
+use crate::{
+    access_modifier::AccessModifer,
+    assembly::Assembly,
+    function_sig::FnSig,
+    method::{Attribute, Method},
+};
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::{Instance, ParamEnv, TyCtxt};
+use rustc_span::def_id::LOCAL_CRATE;
+use rustc_target::spec::abi::Abi;
+/// Library functions declared in `extern` blocks without a `#[link(name = "...")]` attribute are imported from.
+const DEFAULT_NATIVE_LIB: &str = "libc";
+/// Adds P/Invoke declarations of all functions declared in `extern` blocks of the local crate.
+pub fn add_pinvoke_decls(asm: &mut Assembly, tcx: TyCtxt) {
+    for (module_id, module) in tcx.foreign_modules(LOCAL_CRATE) {
+        // Intrinsics are not real functions, and are handled by the codegen itself.
+        if matches!(module.abi, Abi::RustIntrinsic | Abi::PlatformIntrinsic) {
+            continue;
+        }
+        let library: crate::IString = tcx
+            .native_libraries(LOCAL_CRATE)
+            .iter()
+            .find(|lib| lib.foreign_module == Some(*module_id))
+            .map_or(DEFAULT_NATIVE_LIB.into(), |lib| lib.name.to_string().into());
+        for item in &module.foreign_items {
+            if tcx.def_kind(*item) != DefKind::Fn {
+                continue;
+            }
+            let instance = Instance::mono(tcx, *item);
+            let poly_sig = instance.ty(tcx, ParamEnv::reveal_all()).fn_sig(tcx);
+            // Variadic functions can't be called using P/Invoke.
+            if poly_sig.c_variadic() {
+                continue;
+            }
+            let sig = FnSig::from_poly_sig(&poly_sig, tcx, &instance)
+                .expect("Could not get the signature of a foreign function!");
+            let name = crate::utilis::function_name(tcx.symbol_name(instance));
+            let mut method = Method::new(AccessModifer::Private, true, sig, &name, vec![]);
+            method.add_attribute(Attribute::PInvoke {
+                library: library.clone(),
+            });
+            asm.add_method(method);
+        }
+    }
+}
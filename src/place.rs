@@ -251,6 +251,9 @@ fn place_elem_set<'a>(
     match place_elem {
         PlaceElem::Deref => {
             let pointed_type = pointed_type(curr_type);
+            if let PlaceTy::Ty(ptr_type) = curr_type {
+                crate::gc_handle::check_ptr_store(ptr_type, pointed_type, ctx, &method_instance);
+            }
             ptr_set_op(pointed_type.into(), ctx, &method_instance)
         }
         PlaceElem::Field(index, _field_type) => {
@@ -599,6 +602,10 @@ fn ptr_set_op<'ctx>(
             TyKind::Bool => vec![CILOp::STIndI8], // Both Rust bool and a managed bool are 1 byte wide. .NET bools are 4 byte wide only in the context of Marshaling/PInvoke,
             // due to historic reasons(BOOL was an alias for int in early Windows, and it stayed this way.) - FractalFir
             TyKind::Char => vec![CILOp::STIndI32], // always 4 bytes wide: https://doc.rust-lang.org/std/primitive.char.html#representation
+            // Pointers to managed references are byrefs to GC-tracked places.
+            TyKind::Adt(_, _) if crate::gc_handle::is_managed_ref(pointed_type) => {
+                vec![CILOp::STIndRef]
+            }
             TyKind::Adt(_, _) => {
                 vec![CILOp::STObj(
                     crate::r#type::Type::from_ty(pointed_type, tyctx, method_instance).into(),
@@ -651,6 +658,9 @@ pub fn deref_op<'ctx>(
             TyKind::Bool => vec![CILOp::LDIndI8], // Both Rust bool and a managed bool are 1 byte wide. .NET bools are 4 byte wide only in the context of Marshaling/PInvoke,
             // due to historic reasons(BOOL was an alias for int in early Windows, and it stayed this way.) - FractalFir
             TyKind::Char => vec![CILOp::LDIndI32], // always 4 bytes wide: https://doc.rust-lang.org/std/primitive.char.html#representation
+            TyKind::Adt(_, _) if crate::gc_handle::is_managed_ref(derefed_type) => {
+                vec![CILOp::LDIndRef]
+            }
             TyKind::Adt(_, _) => {
                 vec![CILOp::LdObj(
                     crate::r#type::Type::from_ty(derefed_type, tyctx, method_instance).into(),
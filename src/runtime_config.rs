@@ -0,0 +1,200 @@
+use crate::{assembly_exporter::AssemblyInfo, dotnet_metadata::AssemblyRef, IString};
+use std::path::Path;
+/// Target framework used if none is specified. .NET 7 is the first version with `System.Int128`, which is needed by the codegen.
+pub const DEFAULT_TFM: &str = "net7.0";
+/// Policy deciding which runtime version is used when the exact one requested is not installed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RollForward {
+    LatestPatch,
+    Minor,
+    LatestMinor,
+    Major,
+    LatestMajor,
+    Disable,
+}
+impl RollForward {
+    /// Parses a policy name, as used in runtime config files(eg. `LatestMajor`). Case insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::LatestPatch,
+            Self::Minor,
+            Self::LatestMinor,
+            Self::Major,
+            Self::LatestMajor,
+            Self::Disable,
+        ]
+        .into_iter()
+        .find(|policy| policy.name().eq_ignore_ascii_case(name))
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LatestPatch => "LatestPatch",
+            Self::Minor => "Minor",
+            Self::LatestMinor => "LatestMinor",
+            Self::Major => "Major",
+            Self::LatestMajor => "LatestMajor",
+            Self::Disable => "Disable",
+        }
+    }
+}
+/// Describes the runtime needed to run an executable under CoreCLR.
+#[derive(Clone, Debug)]
+pub struct RuntimeConfig {
+    /// Target framework moniker, like `net7.0`.
+    tfm: IString,
+    roll_forward: RollForward,
+}
+impl RuntimeConfig {
+    /// Creates a new runtime config, targeting framework `tfm`. Returns [`None`] if `tfm` is not a .NET Core target framework moniker.
+    pub fn new(tfm: &str, roll_forward: RollForward) -> Option<Self> {
+        framework_version(tfm)?;
+        Some(Self {
+            tfm: tfm.into(),
+            roll_forward,
+        })
+    }
+    /// Contents of the `.runtimeconfig.json` file.
+    pub fn runtime_config_json(&self) -> String {
+        let (major, minor) = framework_version(&self.tfm).unwrap();
+        format!(
+            "{{
+  \"runtimeOptions\": {{
+    \"tfm\": {tfm},
+    \"rollForward\": \"{roll_forward}\",
+    \"framework\": {{
+      \"name\": \"Microsoft.NETCore.App\",
+      \"version\": \"{major}.{minor}.0\"
+    }}
+  }}
+}}
+",
+            tfm = json_string(&self.tfm),
+            roll_forward = self.roll_forward.name()
+        )
+    }
+    /// Contents of the `.deps.json` file, describing an executable named `exe_name` with identity `info`, which references `references`.
+    pub fn deps_json(
+        &self,
+        exe_name: &str,
+        info: &AssemblyInfo,
+        references: &[AssemblyRef],
+    ) -> String {
+        let (major, minor) = framework_version(&self.tfm).unwrap();
+        let target = json_string(&format!(".NETCoreApp,Version=v{major}.{minor}"));
+        let app = json_string(&format!(
+            "{name}/{version}",
+            name = info.name(),
+            version = info.informational_version()
+        ));
+        let reference_ids: Vec<(String, String)> = references
+            .iter()
+            .map(|reference| {
+                let [major, minor, build, revision] = reference.version();
+                (
+                    reference.name().to_owned(),
+                    format!("{major}.{minor}.{build}.{revision}"),
+                )
+            })
+            .collect();
+        let dependencies: Vec<String> = reference_ids
+            .iter()
+            .map(|(name, version)| format!("{}: {}", json_string(name), json_string(version)))
+            .collect();
+        let mut targets = vec![format!(
+            "{app}: {{
+        \"dependencies\": {{ {dependencies} }},
+        \"runtime\": {{ {exe_name}: {{}} }}
+      }}",
+            dependencies = dependencies.join(", "),
+            exe_name = json_string(exe_name)
+        )];
+        let mut libraries = vec![format!(
+            "{app}: {{ \"type\": \"project\", \"serviceable\": false, \"sha512\": \"\" }}"
+        )];
+        for (name, version) in &reference_ids {
+            let id = json_string(&format!("{name}/{version}"));
+            targets.push(format!(
+                "{id}: {{
+        \"runtime\": {{ {dll}: {{}} }}
+      }}",
+                dll = json_string(&format!("{name}.dll"))
+            ));
+            libraries.push(format!(
+                "{id}: {{ \"type\": \"reference\", \"serviceable\": false, \"sha512\": \"\" }}"
+            ));
+        }
+        format!(
+            "{{
+  \"runtimeTarget\": {{ \"name\": {target} }},
+  \"targets\": {{
+    {target}: {{
+      {targets}
+    }}
+  }},
+  \"libraries\": {{
+    {libraries}
+  }}
+}}
+",
+            targets = targets.join(",\n      "),
+            libraries = libraries.join(",\n    ")
+        )
+    }
+    /// Writes `<name>.runtimeconfig.json` and `<name>.deps.json` next to executable `exe_path`.
+    pub fn write(
+        &self,
+        exe_path: &Path,
+        info: &AssemblyInfo,
+        references: &[AssemblyRef],
+    ) -> std::io::Result<()> {
+        let exe_name = exe_path
+            .file_name()
+            .expect("Executable has no name!")
+            .to_string_lossy();
+        std::fs::write(
+            exe_path.with_extension("runtimeconfig.json"),
+            self.runtime_config_json(),
+        )?;
+        std::fs::write(
+            exe_path.with_extension("deps.json"),
+            self.deps_json(&exe_name, info, references),
+        )
+    }
+}
+/// Gets the framework version from a target framework moniker(`net7.0`, `netcoreapp3.1`).
+fn framework_version(tfm: &str) -> Option<(u32, u32)> {
+    let version = tfm
+        .strip_prefix("netcoreapp")
+        .or_else(|| tfm.strip_prefix("net"))?;
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+/// Quotes and escapes `string`, so that it can be used within JSON.
+fn json_string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len() + 2);
+    escaped.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+#[test]
+fn tfm_versions() {
+    assert_eq!(framework_version("net7.0"), Some((7, 0)));
+    assert_eq!(framework_version("netcoreapp3.1"), Some((3, 1)));
+    assert_eq!(framework_version("netstandard2.0"), None);
+}
+#[test]
+fn runtime_config() {
+    let config = RuntimeConfig::new("net8.0", RollForward::LatestMajor).unwrap();
+    let json = config.runtime_config_json();
+    assert!(json.contains("\"tfm\": \"net8.0\""));
+    assert!(json.contains("\"rollForward\": \"LatestMajor\""));
+    assert!(json.contains("\"version\": \"8.0.0\""));
+}
@@ -4,7 +4,7 @@ use crate::r#type::Type;
 use rustc_middle::mir::{CastKind, NullOp};
 use rustc_middle::{
     mir::{Place, Rvalue},
-    ty::{Instance, TyCtxt},
+    ty::{adjustment::PointerCoercion, Instance, ParamEnv, TyCtxt, TyKind},
 };
 pub fn handle_rvalue<'tcx>(
     rvalue: &Rvalue<'tcx>,
@@ -24,6 +24,28 @@ pub fn handle_rvalue<'tcx>(
         Rvalue::AddressOf(_mutability, place) => {
             crate::place::place_adress(place, tcx, method, method_instance)
         }
+        // A pointer to a function item points to its native code.
+        Rvalue::Cast(CastKind::PointerCoercion(PointerCoercion::ReifyFnPointer), operand, _) => {
+            let fn_ty = crate::utilis::monomorphize(&method_instance, operand.ty(method, tcx), tcx);
+            let TyKind::FnDef(def_id, subst) = fn_ty.kind() else {
+                panic!("Can't get a function pointer to {fn_ty:?}, which is not a function definition!");
+            };
+            let instance =
+                Instance::resolve_for_fn_ptr(tcx, ParamEnv::reveal_all(), *def_id, subst)
+                    .expect("Could not resolve the function pointer!");
+            let signature = crate::function_sig::FnSig::from_poly_sig_mono(
+                &fn_ty.fn_sig(tcx),
+                tcx,
+                &method_instance,
+            )
+            .expect("Can't get the function signature");
+            vec![CILOp::LDFtn(CallSite::boxed(
+                None,
+                crate::utilis::function_name(tcx.symbol_name(instance)),
+                signature,
+                true,
+            ))]
+        }
         Rvalue::Cast(CastKind::PointerCoercion(_) | CastKind::PtrToPtr, operand, _) => {
             crate::operand::handle_operand(operand, tcx, method, method_instance)
         }
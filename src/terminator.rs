@@ -8,13 +8,58 @@ use crate::{
     utilis::CTOR_FN_NAME,
     utilis::MANAGED_CALL_FN_NAME,
     utilis::MANAGED_CALL_VIRT_FN_NAME,
+    utilis::{
+        DELEGATE_ENV_FN_NAME, DELEGATE_FN_NAMES, DELEGATE_FROM_FN_PTR_FN_NAME,
+        DELEGATE_NEW_FN_NAME, GC_HANDLE_ALLOC_FN_NAME, GC_HANDLE_FN_NAMES, GC_HANDLE_FREE_FN_NAME,
+        GC_HANDLE_PIN_FN_NAME, GC_HANDLE_TARGET_FN_NAME, GENERIC_ARG_FN_NAMES,
+        GET_PROPERTY_FN_NAME, GET_STATIC_PROPERTY_FN_NAME, LD_FIELD_FN_NAME,
+        LD_STATIC_FIELD_FN_NAME, MANAGED_ARRAY_ADDRESS_FN_NAME, MANAGED_ARRAY_FN_NAMES,
+        MANAGED_ARRAY_GET_FN_NAME, MANAGED_ARRAY_LEN_FN_NAME, MANAGED_ARRAY_NEW_FN_NAME,
+        MANAGED_ARRAY_SET_FN_NAME, MANAGED_MEMBER_FN_NAMES, MANAGED_SPAN_NEW_FN_NAME,
+        SET_PROPERTY_FN_NAME, SET_STATIC_PROPERTY_FN_NAME, ST_FIELD_FN_NAME,
+        ST_STATIC_FIELD_FN_NAME, TRY_CATCH_FN_NAME,
+    },
 };
 use rustc_middle::ty::InstanceDef;
 use rustc_middle::{
-    mir::{Body, Const, ConstValue, Operand, Place, SwitchTargets, Terminator, TerminatorKind},
-    ty::{GenericArg, Instance, ParamEnv, Ty, TyCtxt, TyKind},
+    mir::{
+        BasicBlock, Body, Const, ConstValue, Operand, Place, Rvalue, StatementKind, SwitchTargets,
+        Terminator, TerminatorKind, UnwindAction, RETURN_PLACE,
+    },
+    ty::{GenericArg, Instance, IntTy, ParamEnv, Ty, TyCtxt, TyKind, UintTy},
 };
+use rustc_session::config::OptLevel;
 use rustc_span::def_id::DefId;
+/// Gets the argument count of an interop intrinsic, which is encoded in its name(`rustc_clr_interop_managed_call12_` takes 12 arguments).
+fn interop_argc(function_name: &str, intrinsic: &str) -> u32 {
+    let argc_start = function_name.find(intrinsic).unwrap() + intrinsic.len();
+    let argc: String = function_name[argc_start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    argc.parse()
+        .unwrap_or_else(|_| panic!("Interop intrinsic {function_name:?} has no argument count!"))
+}
+/// Gets the managed class an interop intrinsic refers to, from its `ASSEMBLY`, `CLASS_PATH`, `IS_VALUETYPE` and `Generics`(a tuple)
+/// generic arguments.
+fn managed_class<'ctx>(
+    tyctx: TyCtxt<'ctx>,
+    subst_ref: &[GenericArg<'ctx>],
+    method_instance: &Instance<'ctx>,
+) -> DotnetTypeRef {
+    let asm = garg_to_string(&subst_ref[0], tyctx);
+    let asm = Some(asm).filter(|asm| !asm.is_empty());
+    let class_name = garg_to_string(&subst_ref[1], tyctx);
+    let is_valuetype = crate::utilis::garag_to_bool(&subst_ref[2], tyctx);
+    let mut tpe = DotnetTypeRef::new(asm.as_deref(), &class_name);
+    tpe.set_valuetype(is_valuetype);
+    tpe.set_generics(crate::r#type::managed_generics(
+        &subst_ref[3],
+        tyctx,
+        method_instance,
+    ));
+    tpe
+}
 /// Calls a non-virtual managed function(used for interop)
 fn call_managed<'ctx>(
     tyctx: TyCtxt<'ctx>,
@@ -27,28 +72,18 @@ fn call_managed<'ctx>(
     method_instance: Instance<'ctx>,
     fn_type: &Ty<'ctx>,
 ) -> Vec<CILOp> {
-    let argc_start =
-        function_name.find(MANAGED_CALL_FN_NAME).unwrap() + (MANAGED_CALL_FN_NAME.len());
-    let argc_end = argc_start + function_name[argc_start..].find('_').unwrap();
-    let argc = &function_name[argc_start..argc_end];
-    let argc = argc.parse::<u32>().unwrap();
-    assert!(subst_ref.len() as u32 == argc + 3 || subst_ref.len() as u32 == argc + 4 || true);
+    let argc = interop_argc(function_name, MANAGED_CALL_FN_NAME);
+    assert!(subst_ref.len() as u32 == argc + 4 || subst_ref.len() as u32 == argc + 5 || true);
     assert!(args.len() as u32 == argc);
-    let asm = garg_to_string(&subst_ref[0], tyctx);
-    let asm = Some(asm).filter(|asm| !asm.is_empty());
-    let class_name = garg_to_string(&subst_ref[1], tyctx);
-    let is_valuetype = crate::utilis::garag_to_bool(&subst_ref[2], tyctx);
-    let managed_fn_name = garg_to_string(&subst_ref[3], tyctx);
-    let mut tpe = DotnetTypeRef::new(asm.as_ref().map(|x| x.as_str()), &class_name);
-    tpe.set_valuetype(is_valuetype);
-    let signature = FnSig::from_poly_sig(&fn_type.fn_sig(tyctx), tyctx, &method_instance)
+    let tpe = managed_class(tyctx, subst_ref, &method_instance);
+    let managed_fn_name = garg_to_string(&subst_ref[4], tyctx);
+    let signature = FnSig::managed_from_poly_sig(&fn_type.fn_sig(tyctx), tyctx, &method_instance)
         .expect("Can't get the function signature");
     if argc == 0 {
-        let ret = crate::r#type::Type::Void;
         let call = vec![CILOp::Call(CallSite::boxed(
             Some(tpe.clone()),
             managed_fn_name.into(),
-            FnSig::new(&[], &ret),
+            FnSig::new(&[], signature.output()),
             true,
         ))];
         if *signature.output() == crate::r#type::Type::Void {
@@ -57,7 +92,7 @@ fn call_managed<'ctx>(
             crate::place::place_set(destination, tyctx, call, method, method_instance)
         }
     } else {
-        let is_static = crate::utilis::garag_to_bool(&subst_ref[4], tyctx);
+        let is_static = crate::utilis::garag_to_bool(&subst_ref[5], tyctx);
 
         let mut call = Vec::new();
         for arg in args {
@@ -93,32 +128,22 @@ fn callvirt_managed<'ctx>(
     method_instance: Instance<'ctx>,
     fn_type: &Ty<'ctx>,
 ) -> Vec<CILOp> {
-    let argc_start =
-        function_name.find(MANAGED_CALL_VIRT_FN_NAME).unwrap() + (MANAGED_CALL_VIRT_FN_NAME.len());
-    let argc_end = argc_start + function_name[argc_start..].find('_').unwrap();
-    let argc = &function_name[argc_start..argc_end];
-    let argc = argc.parse::<u32>().unwrap();
-    assert!(subst_ref.len() as u32 == argc + 3 || subst_ref.len() as u32 == argc + 4 || true);
+    let argc = interop_argc(function_name, MANAGED_CALL_VIRT_FN_NAME);
+    assert!(subst_ref.len() as u32 == argc + 4 || subst_ref.len() as u32 == argc + 5 || true);
     assert!(args.len() as u32 == argc);
-    let asm = garg_to_string(&subst_ref[0], tyctx);
-    let asm = Some(asm).filter(|asm| !asm.is_empty());
-    let class_name = garg_to_string(&subst_ref[1], tyctx);
-    let is_valuetype = crate::utilis::garag_to_bool(&subst_ref[2], tyctx);
+    let tpe = managed_class(tyctx, subst_ref, &method_instance);
 
-    let managed_fn_garg = &subst_ref[3];
+    let managed_fn_garg = &subst_ref[4];
     let managed_fn_garg = crate::utilis::monomorphize(&method_instance, *managed_fn_garg, tyctx);
     let managed_fn_name = garg_to_string(&managed_fn_garg, tyctx);
 
-    let mut tpe = DotnetTypeRef::new(asm.as_ref().map(|x| x.as_str()), &class_name);
-    tpe.set_valuetype(is_valuetype);
-    let signature = FnSig::from_poly_sig(&fn_type.fn_sig(tyctx), tyctx, &method_instance)
+    let signature = FnSig::managed_from_poly_sig(&fn_type.fn_sig(tyctx), tyctx, &method_instance)
         .expect("Can't get the function signature");
     if argc == 0 {
-        let ret = crate::r#type::Type::Void;
         let call = vec![CILOp::Call(CallSite::boxed(
             Some(tpe.clone()),
             managed_fn_name.into(),
-            FnSig::new(&[], &ret),
+            FnSig::new(&[], signature.output()),
             true,
         ))];
         if *signature.output() == crate::r#type::Type::Void {
@@ -127,7 +152,7 @@ fn callvirt_managed<'ctx>(
             crate::place::place_set(destination, tyctx, call, method, method_instance)
         }
     } else {
-        let is_static = crate::utilis::garag_to_bool(&subst_ref[4], tyctx);
+        let is_static = crate::utilis::garag_to_bool(&subst_ref[5], tyctx);
 
         let mut call = Vec::new();
         for arg in args {
@@ -162,24 +187,13 @@ fn call_ctor<'ctx>(
     method: &'ctx Body<'ctx>,
     method_instance: Instance<'ctx>,
 ) -> Vec<CILOp> {
-    let argc_start = function_name.find(CTOR_FN_NAME).unwrap() + (CTOR_FN_NAME.len());
-    let argc_end = argc_start + function_name[argc_start..].find('_').unwrap();
-    let argc = &function_name[argc_start..argc_end];
-    let argc = argc.parse::<u32>().unwrap();
+    let argc = interop_argc(function_name, CTOR_FN_NAME);
     // Check that there are enough function path and argument specifers
-    assert!(subst_ref.len() as u32 == argc + 3);
+    assert!(subst_ref.len() as u32 == argc + 4);
     // Check that a proper number of arguments is used
     assert!(args.len() as u32 == argc);
-    // Get the name of the assembly the constructed object resides in
-    let asm = garg_to_string(&subst_ref[0], tyctx);
-    // If empty, make it none(for consitent encoing of No-assembly)
-    let asm = Some(asm).filter(|asm| !asm.is_empty());
-    // Get the name of the constructed object
-    let class_name = garg_to_string(&subst_ref[1], tyctx);
-    // Check if the costructed object is valuetype. TODO: this may be unnecesary. Are valuetpes constructed using newobj?
-    let is_valuetype = crate::utilis::garag_to_bool(&subst_ref[2], tyctx);
-    let mut tpe = DotnetTypeRef::new(asm.as_ref().map(|x| x.as_str()), &class_name);
-    tpe.set_valuetype(is_valuetype);
+    // Get the constructed class. TODO: checking if it is a valuetype may be unnecesary. Are valuetpes constructed using newobj?
+    let tpe = managed_class(tyctx, subst_ref, &method_instance);
     // If no arguments, inputs don't have to be handled, so a simpler call handling is used.
     if argc == 0 {
         crate::place::place_set(
@@ -195,9 +209,12 @@ fn call_ctor<'ctx>(
             method_instance,
         )
     } else {
-        let mut inputs: Vec<_> = subst_ref[3..]
+        let mut inputs: Vec<_> = subst_ref[4..]
             .iter()
-            .map(|ty| crate::r#type::Type::from_ty(ty.as_type().unwrap(), tyctx, &method_instance))
+            .map(|ty| {
+                let ty = monomorphize(&method_instance, ty.as_type().unwrap(), tyctx);
+                crate::r#type::managed_sig_type(ty, tyctx, &method_instance)
+            })
             .collect();
         inputs.insert(0, tpe.clone().into());
         let sig = FnSig::new(&inputs, &crate::r#type::Type::Void);
@@ -219,7 +236,461 @@ fn call_ctor<'ctx>(
         crate::place::place_set(destination, tyctx, call, method, method_instance)
     }
 }
-/// Calls `fn_type` with `args`, placing the return value in destination.
+/// Reads or writes a field or property of a managed type(used for interop). `member_fn` is the name of the intrinsic used.
+/// The member type is taken from the Rust type argument as-is: it is not checked against the metadata of the managed type,
+/// so a mismatch is only reported by the runtime(as a `MissingFieldException` or `MissingMethodException`).
+fn access_managed_member<'ctx>(
+    tyctx: TyCtxt<'ctx>,
+    subst_ref: &[GenericArg<'ctx>],
+    member_fn: &str,
+    args: &[Operand<'ctx>],
+    destination: &Place<'ctx>,
+    method: &'ctx Body<'ctx>,
+    method_instance: Instance<'ctx>,
+) -> Vec<CILOp> {
+    // Check that the owner, member name and member type are all specified.
+    assert_eq!(subst_ref.len(), 6);
+    let tpe = managed_class(tyctx, subst_ref, &method_instance);
+    let class_name = tpe.name_path();
+    let member_name = garg_to_string(&subst_ref[4], tyctx);
+    let member_ty = monomorphize(
+        &method_instance,
+        subst_ref[5]
+            .as_type()
+            .expect("Type of a managed member must be a type!"),
+        tyctx,
+    );
+    let member_tpe = crate::r#type::managed_sig_type(member_ty, tyctx, &method_instance);
+    // Rust-only types have no managed equivalent, so they can't be the type of a managed field or property.
+    // This is the only check done here - the type of the member itself is not known at this point.
+    if matches!(
+        member_tpe,
+        crate::r#type::Type::Void
+            | crate::r#type::Type::Unresolved
+            | crate::r#type::Type::Foreign
+            | crate::r#type::Type::FnDef(_)
+    ) {
+        panic!("{member_ty:?} can't be the type of managed member {class_name}::{member_name}!");
+    }
+    let mut ops = Vec::new();
+    for arg in args {
+        ops.extend(handle_operand(arg, tyctx, method, method_instance));
+    }
+    let field = || {
+        crate::cil_op::FieldDescriptor::boxed(
+            tpe.clone(),
+            member_tpe.clone(),
+            member_name.clone().into(),
+        )
+    };
+    let static_field = || {
+        crate::cil_op::StaticFieldDescriptor::boxed(
+            Some(tpe.clone()),
+            member_tpe.clone(),
+            member_name.clone().into(),
+        )
+    };
+    let getter = |inputs: &[crate::r#type::Type]| FnSig::new(inputs, &member_tpe);
+    let setter = |inputs: &[crate::r#type::Type]| FnSig::new(inputs, &crate::r#type::Type::Void);
+    let (op, is_load) = match member_fn {
+        LD_FIELD_FN_NAME => (CILOp::LDField(field()), true),
+        ST_FIELD_FN_NAME => (CILOp::STField(field()), false),
+        LD_STATIC_FIELD_FN_NAME => (CILOp::LDStaticField(static_field()), true),
+        ST_STATIC_FIELD_FN_NAME => (CILOp::STStaticField(static_field()), false),
+        GET_PROPERTY_FN_NAME => (
+            CILOp::CallVirt(CallSite::boxed(
+                Some(tpe.clone()),
+                format!("get_{member_name}").into(),
+                getter(&[tpe.clone().into()]),
+                false,
+            )),
+            true,
+        ),
+        SET_PROPERTY_FN_NAME => (
+            CILOp::CallVirt(CallSite::boxed(
+                Some(tpe.clone()),
+                format!("set_{member_name}").into(),
+                setter(&[tpe.clone().into(), member_tpe.clone()]),
+                false,
+            )),
+            false,
+        ),
+        GET_STATIC_PROPERTY_FN_NAME => (
+            CILOp::Call(CallSite::boxed(
+                Some(tpe.clone()),
+                format!("get_{member_name}").into(),
+                getter(&[]),
+                true,
+            )),
+            true,
+        ),
+        SET_STATIC_PROPERTY_FN_NAME => (
+            CILOp::Call(CallSite::boxed(
+                Some(tpe.clone()),
+                format!("set_{member_name}").into(),
+                setter(&[member_tpe.clone()]),
+                true,
+            )),
+            false,
+        ),
+        _ => panic!("{member_fn:?} is not a managed member access intrinsic!"),
+    };
+    ops.push(op);
+    if is_load {
+        crate::place::place_set(destination, tyctx, ops, method, method_instance)
+    } else {
+        ops
+    }
+}
+/// Lowers an intrinsic allocating, pinning, reading or freeing a `GCHandle`, which keeps a managed object alive while it is referenced from unmanaged memory.
+fn gc_handle_intrinsic<'ctx>(
+    tyctx: TyCtxt<'ctx>,
+    subst_ref: &[GenericArg<'ctx>],
+    handle_fn: &str,
+    args: &[Operand<'ctx>],
+    destination: &Place<'ctx>,
+    method: &'ctx Body<'ctx>,
+    method_instance: Instance<'ctx>,
+) -> Vec<CILOp> {
+    let target = subst_ref.first().map(|target| {
+        let target = monomorphize(
+            &method_instance,
+            target
+                .as_type()
+                .expect("Target of a GC handle must be a type!"),
+            tyctx,
+        );
+        if !crate::gc_handle::is_managed_ref(target) {
+            panic!("GC handles can only refer to managed objects, but {target:?} is not one!");
+        }
+        target
+    });
+    assert_eq!(args.len(), 1);
+    let mut ops = handle_operand(&args[0], tyctx, method, method_instance);
+    match handle_fn {
+        GC_HANDLE_ALLOC_FN_NAME => ops.extend(crate::gc_handle::alloc_handle()),
+        GC_HANDLE_TARGET_FN_NAME => {
+            let target = target.expect("The target of a GC handle must be specified!");
+            ops.extend(crate::gc_handle::handle_target(
+                crate::r#type::Type::from_ty(target, tyctx, &method_instance),
+            ))
+        }
+        GC_HANDLE_PIN_FN_NAME => ops.extend(crate::gc_handle::alloc_pinned_handle()),
+        GC_HANDLE_FREE_FN_NAME => {
+            ops.extend(crate::gc_handle::free_handle());
+            return ops;
+        }
+        _ => panic!("{handle_fn:?} is not a GC handle intrinsic!"),
+    }
+    crate::place::place_set(destination, tyctx, ops, method, method_instance)
+}
+/// Loads the indices of an element of a `dimensions`-dimensional managed array. `indices` calculates a pointer to an array of `usize` indices.
+fn array_indices(indices: &[CILOp], dimensions: u64) -> Vec<CILOp> {
+    let mut ops = Vec::new();
+    for dimension in 0..dimensions {
+        ops.extend(indices.iter().cloned());
+        if dimension > 0 {
+            ops.extend([
+                CILOp::LdcI32(dimension as i32),
+                CILOp::SizeOf(crate::r#type::Type::USize.into()),
+                CILOp::Mul,
+                CILOp::Add,
+            ]);
+        }
+        ops.extend([CILOp::LDIndISize, CILOp::ConvI32(false)]);
+    }
+    ops
+}
+/// Lowers an intrinsic creating a managed array, or accessing its elements.
+fn managed_array_intrinsic<'ctx>(
+    tyctx: TyCtxt<'ctx>,
+    signature: &FnSig,
+    array_fn: &str,
+    args: &[Operand<'ctx>],
+    destination: &Place<'ctx>,
+    method: &'ctx Body<'ctx>,
+    method_instance: Instance<'ctx>,
+) -> Vec<CILOp> {
+    // `new` returns the array, all other intrinsics take it as their first argument.
+    let array_tpe = if array_fn == MANAGED_ARRAY_NEW_FN_NAME {
+        signature.output()
+    } else {
+        &signature.inputs()[0]
+    };
+    let crate::r#type::Type::DotnetArray(array) = array_tpe else {
+        panic!("{array_tpe:?} is not a managed array!");
+    };
+    let operand = |idx: usize| handle_operand(&args[idx], tyctx, method, method_instance);
+    let (mut ops, is_load) = match array_fn {
+        MANAGED_ARRAY_NEW_FN_NAME => {
+            let mut ops = array_indices(&operand(0), array.dimensions);
+            ops.push(CILOp::NewArr(array.clone()));
+            (ops, true)
+        }
+        MANAGED_ARRAY_LEN_FN_NAME => {
+            let mut ops = operand(0);
+            ops.extend([CILOp::LDLen(array.clone()), CILOp::ConvUSize(false)]);
+            (ops, true)
+        }
+        MANAGED_ARRAY_GET_FN_NAME => {
+            let mut ops = operand(0);
+            ops.extend(array_indices(&operand(1), array.dimensions));
+            ops.push(CILOp::LDElem(array.clone()));
+            (ops, true)
+        }
+        MANAGED_ARRAY_SET_FN_NAME => {
+            let mut ops = operand(0);
+            ops.extend(array_indices(&operand(1), array.dimensions));
+            ops.extend(operand(2));
+            ops.push(CILOp::STElem(array.clone()));
+            (ops, false)
+        }
+        MANAGED_ARRAY_ADDRESS_FN_NAME => {
+            let mut ops = operand(0);
+            ops.extend(array_indices(&operand(1), array.dimensions));
+            // The array must be pinned, or the GC could move it while the pointer is in use.
+            ops.extend([CILOp::LDElemAdress(array.clone()), CILOp::ConvUSize(false)]);
+            (ops, true)
+        }
+        _ => panic!("{array_fn:?} is not a managed array intrinsic!"),
+    };
+    if is_load {
+        ops = crate::place::place_set(destination, tyctx, ops, method, method_instance);
+    }
+    ops
+}
+/// Creates a `Span<T>` or `ReadOnlySpan<T>`, refering to `len` elements of unmanaged memory starting at `ptr`.
+fn new_managed_span<'ctx>(
+    tyctx: TyCtxt<'ctx>,
+    subst_ref: &[GenericArg<'ctx>],
+    signature: &FnSig,
+    args: &[Operand<'ctx>],
+    destination: &Place<'ctx>,
+    method: &'ctx Body<'ctx>,
+    method_instance: Instance<'ctx>,
+) -> Vec<CILOp> {
+    let element = monomorphize(
+        &method_instance,
+        subst_ref[0]
+            .as_type()
+            .expect("Span element type must be a type!"),
+        tyctx,
+    );
+    // Spans over unmanaged memory can't contain references the GC would need to track.
+    if crate::gc_handle::is_managed_ref(element) {
+        panic!("Spans over Rust memory can't contain managed references, but {element:?} is one!");
+    }
+    let crate::r#type::Type::DotnetType(span) = signature.output() else {
+        panic!("{:?} is not a managed span!", signature.output());
+    };
+    assert_eq!(args.len(), 2);
+    let mut ops = handle_operand(&args[0], tyctx, method, method_instance);
+    ops.extend(handle_operand(&args[1], tyctx, method, method_instance));
+    ops.extend([
+        CILOp::ConvI32(true),
+        CILOp::NewObj(CallSite::boxed(
+            Some(span.as_ref().clone()),
+            ".ctor".into(),
+            FnSig::new(
+                &[
+                    signature.output().clone(),
+                    crate::r#type::Type::Ptr(crate::r#type::Type::Void.into()),
+                    crate::r#type::Type::I32,
+                ],
+                &crate::r#type::Type::Void,
+            ),
+            false,
+        )),
+    ]);
+    crate::place::place_set(destination, tyctx, ops, method, method_instance)
+}
+/// Handles the delegate intrinsic `delegate_fn`. Delegates are created from function pointers, using the `(object, native int)`
+/// constructor all delegate types have. The target object is either null(for static functions), or a `RustClosureEnv` carrying the
+/// environment of a Rust closure, which drops the environment when finalized. In the later case, the function receives the target as its
+/// first argument.
+fn delegate_intrinsic<'ctx>(
+    tyctx: TyCtxt<'ctx>,
+    signature: &FnSig,
+    delegate_fn: &str,
+    args: &[Operand<'ctx>],
+    destination: &Place<'ctx>,
+    method: &'ctx Body<'ctx>,
+    method_instance: Instance<'ctx>,
+) -> Vec<CILOp> {
+    let ctor = |delegate: &crate::r#type::Type| {
+        let crate::r#type::Type::DotnetType(delegate_tpe) = delegate else {
+            panic!("{delegate:?} is not a managed delegate!");
+        };
+        let object: crate::r#type::Type =
+            DotnetTypeRef::new(Some("System.Runtime"), "System.Object").into();
+        CILOp::NewObj(CallSite::boxed(
+            Some(delegate_tpe.as_ref().clone()),
+            ".ctor".into(),
+            FnSig::new(
+                &[delegate.clone(), object, crate::r#type::Type::ISize],
+                &crate::r#type::Type::Void,
+            ),
+            false,
+        ))
+    };
+    let ops = match delegate_fn {
+        DELEGATE_FROM_FN_PTR_FN_NAME => {
+            assert_eq!(args.len(), 1);
+            let mut ops = vec![CILOp::LdNull];
+            ops.extend(handle_operand(&args[0], tyctx, method, method_instance));
+            ops.push(ctor(signature.output()));
+            ops
+        }
+        DELEGATE_NEW_FN_NAME => {
+            assert_eq!(args.len(), 3);
+            let env_class = crate::utilis::closure_env_class();
+            let mut ops = handle_operand(&args[0], tyctx, method, method_instance);
+            ops.extend(handle_operand(&args[1], tyctx, method, method_instance));
+            ops.push(CILOp::NewObj(CallSite::boxed(
+                Some(env_class.clone()),
+                ".ctor".into(),
+                FnSig::new(
+                    &[
+                        env_class.into(),
+                        crate::r#type::Type::ISize,
+                        crate::r#type::Type::ISize,
+                    ],
+                    &crate::r#type::Type::Void,
+                ),
+                false,
+            )));
+            ops.extend(handle_operand(&args[2], tyctx, method, method_instance));
+            ops.push(ctor(signature.output()));
+            ops
+        }
+        DELEGATE_ENV_FN_NAME => {
+            assert_eq!(args.len(), 1);
+            let env_class = crate::utilis::closure_env_class();
+            let mut ops = handle_operand(&args[0], tyctx, method, method_instance);
+            ops.extend([
+                CILOp::CastClass(crate::r#type::Type::from(env_class.clone()).into()),
+                CILOp::LDField(crate::cil_op::FieldDescriptor::boxed(
+                    env_class,
+                    crate::r#type::Type::ISize,
+                    "env".into(),
+                )),
+            ]);
+            ops
+        }
+        _ => panic!("Unknown delegate intrinsic {delegate_fn:?}!"),
+    };
+    crate::place::place_set(destination, tyctx, ops, method, method_instance)
+}
+/// Calls closure `args[0]` inside a protected region. The result of the closure is placed in `destination` as `Ok`, and exceptions of
+/// the managed class specified by `subst_ref` are caught and placed there as `Err`, in the form of a `GCHandle`.
+fn try_catch<'ctx>(
+    tyctx: TyCtxt<'ctx>,
+    subst_ref: &[GenericArg<'ctx>],
+    args: &[Operand<'ctx>],
+    destination: &Place<'ctx>,
+    method: &'ctx Body<'ctx>,
+    method_instance: Instance<'ctx>,
+    next_label: &mut u32,
+) -> Vec<CILOp> {
+    assert_eq!(subst_ref.len(), 4);
+    let asm = garg_to_string(&subst_ref[0], tyctx);
+    let asm = Some(asm).filter(|asm| !asm.is_empty());
+    let class_name = garg_to_string(&subst_ref[1], tyctx);
+    let mut exception = DotnetTypeRef::new(asm.as_ref().map(|x| x.as_str()), &class_name);
+    exception.set_valuetype(false);
+    let closure = monomorphize(
+        &method_instance,
+        subst_ref[3]
+            .as_type()
+            .expect("The protected closure must be a type!"),
+        tyctx,
+    );
+    // The closure is called trough `FnOnce::call_once`, like the body of the intrinsic does.
+    let fn_once = tyctx
+        .lang_items()
+        .fn_once_trait()
+        .expect("Can't find the `FnOnce` trait!");
+    let call_once = tyctx
+        .associated_items(fn_once)
+        .in_definition_order()
+        .find(|item| item.kind == rustc_middle::ty::AssocKind::Fn)
+        .expect("`FnOnce` has no `call_once` method!")
+        .def_id;
+    let call_once = Ty::new_fn_def(tyctx, call_once, [closure, tyctx.types.unit]);
+    let TyKind::FnDef(call_once_id, call_once_subst) = call_once.kind() else {
+        panic!("{call_once:?} is not a function definition!");
+    };
+    let instance = Instance::resolve(
+        tyctx,
+        ParamEnv::reveal_all(),
+        *call_once_id,
+        call_once_subst,
+    )
+    .expect("Invalid function def")
+    .expect("No function?");
+    let signature = FnSig::from_poly_sig_mono(&call_once.fn_sig(tyctx), tyctx, &method_instance)
+        .expect("Can't get the function signature");
+    let mut call = handle_operand(&args[0], tyctx, method, method_instance);
+    call.extend([
+        CILOp::NewTMPLocal(crate::r#type::Type::Void.into()),
+        CILOp::LoadTMPLocal,
+        CILOp::FreeTMPLocal,
+        CILOp::Call(CallSite::boxed(
+            None,
+            crate::utilis::function_name(tyctx.symbol_name(instance)),
+            signature,
+            true,
+        )),
+    ]);
+    let result_ty = monomorphize(&method_instance, destination.ty(method, tyctx).ty, tyctx);
+    let TyKind::Adt(result_def, result_subst) = result_ty.kind() else {
+        panic!("{result_ty:?} is not a `Result`!");
+    };
+    let variant = |variant_idx: u32, value: Vec<CILOp>| {
+        crate::aggregate::aggregate_adt(
+            tyctx,
+            destination,
+            method,
+            result_def,
+            result_ty,
+            result_subst,
+            variant_idx,
+            vec![(0, value)],
+            method_instance,
+            &None,
+        )
+    };
+    let end = *next_label;
+    *next_label += 1;
+    let mut ops = vec![CILOp::BeginTry];
+    ops.extend(variant(0, call));
+    ops.extend([
+        CILOp::Pop,
+        CILOp::Leave(end),
+        CILOp::EndTry,
+        // Rust panics are exceptions too, but they must keep unwinding, so they are caught by the first handler and rethrown.
+        CILOp::BeginCatch(crate::utilis::rust_panic_class().into()),
+        CILOp::Rethrow,
+        CILOp::EndCatch,
+        CILOp::BeginCatch(exception.into()),
+        CILOp::NewTMPLocal(crate::r#type::Type::ISize.into()),
+    ]);
+    let mut handle = crate::gc_handle::alloc_handle();
+    handle.push(CILOp::SetTMPLocal);
+    ops.extend(handle);
+    ops.extend(variant(1, vec![CILOp::LoadTMPLocal]));
+    ops.extend([
+        CILOp::FreeTMPLocal,
+        CILOp::Pop,
+        CILOp::Leave(end),
+        CILOp::EndCatch,
+        CILOp::Label(end),
+    ]);
+    ops
+}
+/// Calls `fn_type` with `args`, placing the return value in destination. If `tail_call` is true, the result of the call is returned from
+/// the current method instead, and calls to the current method are turned into a jump to its beginning. Labels needed by the call are
+/// allocated from `next_label`.
 fn call<'ctx>(
     fn_type: &Ty<'ctx>,
     body: &'ctx Body<'ctx>,
@@ -227,6 +698,8 @@ fn call<'ctx>(
     args: &[Operand<'ctx>],
     destination: &Place<'ctx>,
     method_instance: Instance<'ctx>,
+    tail_call: bool,
+    next_label: &mut u32,
 ) -> Vec<CILOp> {
     let (instance, def_id, subst_ref) = if let TyKind::FnDef(def_id, subst_ref) = fn_type.kind() {
         let env = ParamEnv::reveal_all();
@@ -253,6 +726,92 @@ fn call<'ctx>(
             body,
             method_instance,
         );
+    } else if let Some(member_fn) = MANAGED_MEMBER_FN_NAMES
+        .iter()
+        .find(|member_fn| function_name.contains(*member_fn))
+    {
+        // Field or property access (for interop)
+        return access_managed_member(
+            tyctx,
+            subst_ref,
+            member_fn,
+            args,
+            destination,
+            body,
+            method_instance,
+        );
+    } else if let Some(handle_fn) = GC_HANDLE_FN_NAMES
+        .iter()
+        .find(|handle_fn| function_name.contains(*handle_fn))
+    {
+        // GC handles (for interop)
+        return gc_handle_intrinsic(
+            tyctx,
+            subst_ref,
+            handle_fn,
+            args,
+            destination,
+            body,
+            method_instance,
+        );
+    } else if let Some(array_fn) = MANAGED_ARRAY_FN_NAMES
+        .iter()
+        .find(|array_fn| function_name.contains(*array_fn))
+    {
+        // Managed arrays (for interop)
+        return managed_array_intrinsic(
+            tyctx,
+            &signature,
+            array_fn,
+            args,
+            destination,
+            body,
+            method_instance,
+        );
+    } else if let Some(delegate_fn) = DELEGATE_FN_NAMES
+        .iter()
+        .find(|delegate_fn| function_name.contains(*delegate_fn))
+    {
+        // Delegates (for interop)
+        return delegate_intrinsic(
+            tyctx,
+            &signature,
+            delegate_fn,
+            args,
+            destination,
+            body,
+            method_instance,
+        );
+    } else if GENERIC_ARG_FN_NAMES
+        .iter()
+        .any(|generic_arg_fn| function_name.contains(generic_arg_fn))
+    {
+        // Wrapping or unwrapping generic arguments of managed classes (for interop). Both have the same .NET type, so this is a copy.
+        assert_eq!(args.len(), 1);
+        let ops = handle_operand(&args[0], tyctx, body, method_instance);
+        return crate::place::place_set(destination, tyctx, ops, body, method_instance);
+    } else if function_name.contains(TRY_CATCH_FN_NAME) {
+        // Catching managed exceptions (for interop)
+        return try_catch(
+            tyctx,
+            subst_ref,
+            args,
+            destination,
+            body,
+            method_instance,
+            next_label,
+        );
+    } else if function_name.contains(MANAGED_SPAN_NEW_FN_NAME) {
+        // Spans over Rust memory (for interop)
+        return new_managed_span(
+            tyctx,
+            subst_ref,
+            &signature,
+            args,
+            destination,
+            body,
+            method_instance,
+        );
     } else if function_name.contains(MANAGED_CALL_VIRT_FN_NAME) {
         // Virtual (for interop)
         return callvirt_managed(
@@ -290,12 +849,21 @@ fn call<'ctx>(
         ));
     }
     let is_void = matches!(signature.output(), crate::r#type::Type::Void);
-    call.push(CILOp::Call(CallSite::boxed(
-        None,
-        function_name,
-        signature,
-        true,
-    )));
+    if tail_call && instance == method_instance {
+        // Self-recursive tail call: overwrite the arguments, and jump back to the first basic block.
+        for arg in (0..args.len()).rev() {
+            call.push(CILOp::STArg(arg as u32));
+        }
+        call.push(CILOp::GoTo(0));
+        return call;
+    }
+    let site = CallSite::boxed(None, function_name, signature, true);
+    if tail_call && !site.is_nop() {
+        call.push(CILOp::TailCall(site));
+        call.push(CILOp::Ret);
+        return call;
+    }
+    call.push(CILOp::Call(site));
     // Hande
     if is_void {
         call
@@ -303,12 +871,14 @@ fn call<'ctx>(
         crate::place::place_set(destination, tyctx, call, body, method_instance)
     }
 }
+/// Converts a terminator into CIL ops. Labels needed internaly by the terminator are allocated from `next_label`, which must not collide with basic block ids.
 pub fn handle_terminator<'ctx>(
     terminator: &Terminator<'ctx>,
     body: &'ctx Body<'ctx>,
     tyctx: TyCtxt<'ctx>,
     method: &rustc_middle::mir::Body<'ctx>,
     method_instance: Instance<'ctx>,
+    next_label: &mut u32,
 ) -> Vec<CILOp> {
     match &terminator.kind {
         TerminatorKind::Call {
@@ -316,11 +886,12 @@ pub fn handle_terminator<'ctx>(
             args,
             destination,
             target,
-            unwind: _,
+            unwind,
             call_source: _,
             fn_span: _,
         } => {
             let mut ops = Vec::new();
+            let tail_call = is_tail_call(body, destination, *target, unwind, tyctx);
             match func {
                 Operand::Constant(fn_const) => {
                     let fn_ty = fn_const.ty();
@@ -329,13 +900,26 @@ pub fn handle_terminator<'ctx>(
                         "fn_ty{fn_ty:?} in call is not a function type!"
                     );
                     let fn_ty = monomorphize(&method_instance, fn_ty, tyctx);
-                    let call_ops = call(&fn_ty, body, tyctx, args, destination, method_instance);
+                    let call_ops = call(
+                        &fn_ty,
+                        body,
+                        tyctx,
+                        args,
+                        destination,
+                        method_instance,
+                        tail_call,
+                        next_label,
+                    );
                     ops.extend(call_ops);
                 }
                 _ => panic!("called func must be const!"),
             }
+            // Tail calls never return to the caller, so there is no target to jump to.
+            let returned = matches!(ops.last(), Some(CILOp::Ret | CILOp::GoTo(_)));
             if let Some(target) = target {
-                ops.push(CILOp::GoTo(target.as_u32()));
+                if !returned {
+                    ops.push(CILOp::GoTo(target.as_u32()));
+                }
             }
             ops
         }
@@ -351,7 +935,7 @@ pub fn handle_terminator<'ctx>(
         TerminatorKind::SwitchInt { discr, targets } => {
             let ty = crate::utilis::monomorphize(&method_instance, discr.ty(method, tyctx), tyctx);
             let discr = crate::operand::handle_operand(discr, tyctx, method, method_instance);
-            handle_switch(ty, discr, targets)
+            handle_switch(ty, discr, targets, next_label)
         }
         TerminatorKind::Assert {
             cond,
@@ -412,6 +996,66 @@ pub fn handle_terminator<'ctx>(
         _ => todo!("Unhandled terminator kind {kind:?}", kind = terminator.kind),
     }
 }
+/// Checks if a call can be turned into a tail call: its result must be returned straight away, with no cleanup pending.
+/// Tail calls are only emitted when optimizations are enabled.
+fn is_tail_call<'ctx>(
+    body: &Body<'ctx>,
+    destination: &Place<'ctx>,
+    target: Option<BasicBlock>,
+    unwind: &UnwindAction,
+    tyctx: TyCtxt<'ctx>,
+) -> bool {
+    if tyctx.sess.opts.optimize == OptLevel::No {
+        return false;
+    }
+    if !matches!(unwind, UnwindAction::Continue | UnwindAction::Unreachable) {
+        return false;
+    }
+    if destination.local != RETURN_PLACE || !destination.projection.is_empty() {
+        return false;
+    }
+    let Some(target) = target else {
+        return false;
+    };
+    let target_block = &body.basic_blocks[target];
+    let only_storage_markers = target_block.statements.iter().all(|statement| {
+        matches!(
+            statement.kind,
+            StatementKind::StorageLive(_) | StatementKind::StorageDead(_) | StatementKind::Nop
+        )
+    });
+    if !only_storage_markers || !matches!(target_block.terminator().kind, TerminatorKind::Return) {
+        return false;
+    }
+    // Adresses of locals would dangle once the frame is discarded(or reused, for self-recursive calls).
+    !takes_local_adress(body)
+}
+/// Checks if `body` ever takes the adress of one of its own locals.
+fn takes_local_adress(body: &Body) -> bool {
+    body.basic_blocks
+        .iter()
+        .flat_map(|block| &block.statements)
+        .any(|statement| match &statement.kind {
+            StatementKind::Assign(place_rvalue) => match &place_rvalue.1 {
+                Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) => !place.is_indirect(),
+                _ => false,
+            },
+            _ => false,
+        })
+}
+/// Ops throwing a new Rust panic exception.
+fn rust_panic() -> Vec<CILOp> {
+    let panic = crate::utilis::rust_panic_class();
+    vec![
+        CILOp::NewObj(CallSite::boxed(
+            Some(panic.clone()),
+            ".ctor".into(),
+            FnSig::new(&[panic.into()], &crate::r#type::Type::Void),
+            false,
+        )),
+        CILOp::Throw,
+    ]
+}
 fn throw_assert_msg<'ctx>(
     msg: &rustc_middle::mir::AssertMessage<'ctx>,
     tyctx: TyCtxt<'ctx>,
@@ -421,7 +1065,7 @@ fn throw_assert_msg<'ctx>(
     use rustc_middle::mir::AssertKind;
     // Assertion messages cause miscomplations.
     if true {
-        return vec![CILOp::LdNull, CILOp::Throw];
+        return rust_panic();
     };
     match msg {
         AssertKind::BoundsCheck { len, index } => {
@@ -623,19 +1267,199 @@ fn throw_assert_msg<'ctx>(
         _ => todo!("unsuported assertion message:{msg:?}"),
     }
 }
-fn handle_switch(ty: Ty, discr: Vec<CILOp>, switch: &SwitchTargets) -> Vec<CILOp> {
+/// MIR only allows switching on integers, `bool`s and `char`s(enums are switched on through their discriminant), so any other type is a bug.
+fn invalid_switch_discriminant(ty: Ty) -> ! {
+    unreachable!(
+        "switch discriminant of type {ty:?}, while MIR only allows integers, bools and chars"
+    )
+}
+/// Minimal number of cases for which a jump table is used.
+const MIN_JUMP_TABLE_CASES: usize = 3;
+/// Maximal number of entries in a jump table.
+const MAX_JUMP_TABLE_LEN: u128 = 1024;
+/// Switches with at most this many cases are lowered to a simple chain of comparisons.
+const MAX_LINEAR_SWITCH_CASES: usize = 4;
+fn handle_switch(
+    ty: Ty,
+    mut discr: Vec<CILOp>,
+    switch: &SwitchTargets,
+    next_label: &mut u32,
+) -> Vec<CILOp> {
+    match ty.kind() {
+        TyKind::Int(_) | TyKind::Uint(_) | TyKind::Bool => (),
+        // Chars may be either 32 or 64 bit wide on the stack, so they are always converted to 32 bits.
+        TyKind::Char => discr.push(CILOp::ConvU32(false)),
+        _ => invalid_switch_discriminant(ty),
+    }
+    let mut cases: Vec<(u128, u32)> = switch
+        .iter()
+        .map(|(value, target)| (value, target.into()))
+        .collect();
+    let otherwise: u32 = switch.otherwise().into();
+    cases.sort_by(|(a, _), (b, _)| cmp_switch_values(ty, *a, *b));
+    if let Some(ops) = switch_jump_table(ty, &discr, &cases, otherwise) {
+        return ops;
+    }
+    switch_binary_search(ty, &discr, &cases, otherwise, next_label)
+}
+/// Lowers a switch over a dense range of values into a single CIL `switch` instruction. Returns [`None`] if the values are not dense enough.
+fn switch_jump_table(
+    ty: Ty,
+    discr: &[CILOp],
+    cases: &[(u128, u32)],
+    otherwise: u32,
+) -> Option<Vec<CILOp>> {
+    let bits = switch_value_bits(ty);
+    if cases.len() < MIN_JUMP_TABLE_CASES || bits > 64 {
+        return None;
+    }
+    let values: Vec<i128> = cases
+        .iter()
+        .map(|(value, _)| switch_value_as_i128(ty, *value))
+        .collect();
+    let (min, max) = (values[0], values[values.len() - 1]);
+    let len = (max - min) as u128 + 1;
+    if len > MAX_JUMP_TABLE_LEN || len > 2 * cases.len() as u128 {
+        return None;
+    }
+    let mut table = vec![otherwise; len as usize];
+    for (value, (_, target)) in values.iter().zip(cases) {
+        table[(value - min) as usize] = *target;
+    }
     let mut ops = Vec::new();
-    for (value, target) in switch.iter() {
+    if bits > 32 {
+        // `switch` only looks at the lower 32 bits, so values outside of the table must be filtered out first.
+        ops.extend(load_switch_value(ty, (max - min) as u128));
         ops.extend(discr.iter().cloned());
-        ops.extend(match ty.kind() {
-            TyKind::Int(int) => crate::constant::load_const_int(value, int),
-            TyKind::Uint(uint) => crate::constant::load_const_uint(value, uint),
-            TyKind::Bool => vec![CILOp::LdcI32(value as u8 as i32)],
-            _ => todo!("Unsuported switch discriminant type {ty:?}"),
-        });
-        //ops.push(CILOp::LdcI64(value as i64));
-        ops.push(CILOp::BEq(target.into()));
-    }
-    ops.push(CILOp::GoTo(switch.otherwise().into()));
+        ops.extend(load_switch_value(ty, min as u128));
+        ops.push(CILOp::Sub);
+        ops.push(CILOp::BLtUn(otherwise));
+    }
+    ops.extend(discr.iter().cloned());
+    if min != 0 {
+        ops.extend(load_switch_value(ty, min as u128));
+        ops.push(CILOp::Sub);
+    }
+    if bits > 32 {
+        ops.push(CILOp::ConvU32(false));
+    }
+    ops.push(CILOp::Switch(table));
+    ops.push(CILOp::GoTo(otherwise));
+    Some(ops)
+}
+/// Lowers a switch into a binary search over sorted `cases`, with short linear comparison chains at the leafs.
+fn switch_binary_search(
+    ty: Ty,
+    discr: &[CILOp],
+    cases: &[(u128, u32)],
+    otherwise: u32,
+    next_label: &mut u32,
+) -> Vec<CILOp> {
+    let mut ops = Vec::new();
+    if cases.len() <= MAX_LINEAR_SWITCH_CASES {
+        for (value, target) in cases {
+            ops.extend(discr.iter().cloned());
+            ops.extend(load_switch_value(ty, *value));
+            ops.extend(branch_if_eq(ty, *target));
+        }
+        ops.push(CILOp::GoTo(otherwise));
+        return ops;
+    }
+    let mid = cases.len() / 2;
+    let lower_half = *next_label;
+    *next_label += 1;
+    ops.extend(discr.iter().cloned());
+    ops.extend(load_switch_value(ty, cases[mid].0));
+    ops.extend(branch_if_lt(ty, lower_half));
+    ops.extend(switch_binary_search(
+        ty,
+        discr,
+        &cases[mid..],
+        otherwise,
+        next_label,
+    ));
+    ops.push(CILOp::Label(lower_half));
+    ops.extend(switch_binary_search(
+        ty,
+        discr,
+        &cases[..mid],
+        otherwise,
+        next_label,
+    ));
     ops
 }
+/// Returns the size of a switch discriminant, in bits.
+fn switch_value_bits(ty: Ty) -> u64 {
+    match ty.kind() {
+        TyKind::Int(int) => int.bit_width().unwrap_or(64),
+        TyKind::Uint(uint) => uint.bit_width().unwrap_or(64),
+        TyKind::Bool => 8,
+        TyKind::Char => 32,
+        _ => invalid_switch_discriminant(ty),
+    }
+}
+/// Converts the raw bits of a switch value into an i128, sign-extending them if `ty` is signed. Must not be used for 128 bit types.
+fn switch_value_as_i128(ty: Ty, value: u128) -> i128 {
+    let bits = switch_value_bits(ty);
+    debug_assert!(bits <= 64);
+    if ty.is_signed() {
+        let shift = 128 - bits;
+        ((value << shift) as i128) >> shift
+    } else {
+        value as i128
+    }
+}
+/// Compares 2 switch values, taking the signedness of `ty` into account.
+fn cmp_switch_values(ty: Ty, a: u128, b: u128) -> std::cmp::Ordering {
+    if switch_value_bits(ty) == 128 && ty.is_signed() {
+        (a as i128).cmp(&(b as i128))
+    } else if switch_value_bits(ty) == 128 {
+        a.cmp(&b)
+    } else {
+        switch_value_as_i128(ty, a).cmp(&switch_value_as_i128(ty, b))
+    }
+}
+/// Loads a constant switch value of type `ty`.
+fn load_switch_value(ty: Ty, value: u128) -> Vec<CILOp> {
+    match ty.kind() {
+        TyKind::Int(int) => crate::constant::load_const_int(value, int),
+        TyKind::Uint(uint) => crate::constant::load_const_uint(value, uint),
+        TyKind::Bool => vec![CILOp::LdcI32(value as u8 as i32)],
+        TyKind::Char => vec![CILOp::LdcI32(value as u32 as i32)],
+        _ => invalid_switch_discriminant(ty),
+    }
+}
+/// Jumps to `target` if the 2 switch values on top of the stack are equal.
+fn branch_if_eq(ty: Ty, target: u32) -> Vec<CILOp> {
+    match ty.kind() {
+        TyKind::Int(IntTy::I128) | TyKind::Uint(UintTy::U128) => {
+            int128_operator(ty, "op_Equality", target)
+        }
+        _ => vec![CILOp::BEq(target)],
+    }
+}
+/// Jumps to `target` if the lower of the 2 switch values on top of the stack is less than the upper one.
+fn branch_if_lt(ty: Ty, target: u32) -> Vec<CILOp> {
+    match ty.kind() {
+        TyKind::Int(IntTy::I128) | TyKind::Uint(UintTy::U128) => {
+            int128_operator(ty, "op_LessThan", target)
+        }
+        TyKind::Int(_) => vec![CILOp::BLt(target)],
+        _ => vec![CILOp::BLtUn(target)],
+    }
+}
+/// 128 bit intiegers are .NET structs, so they have to be compared by calling their comparison operators.
+fn int128_operator(ty: Ty, operator: &str, target: u32) -> Vec<CILOp> {
+    let (class, tpe) = if ty.is_signed() {
+        ("System.Int128", crate::r#type::Type::I128)
+    } else {
+        ("System.UInt128", crate::r#type::Type::U128)
+    };
+    let class = DotnetTypeRef::new(Some("System.Runtime"), class);
+    let sig = FnSig::new(&[tpe.clone(), tpe], &crate::r#type::Type::Bool);
+    vec![
+        CILOp::Call(CallSite::boxed(Some(class), operator.into(), sig, true)),
+        CILOp::LdcI32(1),
+        CILOp::BEq(target),
+    ]
+}
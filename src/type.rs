@@ -43,6 +43,9 @@ pub enum Type {
     DotnetChar,
     /// Rust FnDefs
     FnDef(Box<CallSite>),
+    /// A pinned managed reference to the inner type. Only valid as the type of a local: the GC will not move the referenced value
+    /// while the local holds the reference.
+    PinnedRef(Box<Self>),
 }
 #[derive(Serialize, Deserialize, PartialEq, Clone, Eq, Hash, Debug)]
 pub struct DotnetArray {
@@ -467,10 +470,13 @@ impl From<DotnetTypeRef> for Type {
         Self::DotnetType(Box::new(value))
     }
 }
-const INTEROP_CLASS_TPE_NAME: &str = "RustcCLRInteropManagedClass";
+pub(crate) const INTEROP_CLASS_TPE_NAME: &str = "RustcCLRInteropManagedClass";
 const INTEROP_STRUCT_TPE_NAME: &str = "RustcCLRInteropManagedStruct";
 const INTEROP_CHR_TPE_NAME: &str = "RustcCLRInteropManagedChar";
-const INTEROP_ARR_TPE_NAME: &str = "RustcCLRInteropManagedArray";
+pub(crate) const INTEROP_ARR_TPE_NAME: &str = "RustcCLRInteropManagedArray";
+const INTEROP_SPAN_TPE_NAME: &str = "RustcCLRInteropManagedSpan";
+pub(crate) const INTEROP_DELEGATE_TPE_NAME: &str = "RustcCLRInteropManagedDelegate";
+const INTEROP_GENERIC_ARG_TPE_NAME: &str = "RustcCLRInteropManagedGenericArg";
 fn is_name_magic(name: &str) -> bool {
     name.contains("RustcCLRInteropManaged")
 }
@@ -502,8 +508,8 @@ fn magic_type<'tyctx>(
     let name = name;
     match name {
         INTEROP_CLASS_TPE_NAME => {
-            if subst.len() != 2 {
-                panic!("MAnaged object reference must have exactly 2 generic arguments!");
+            if subst.len() != 3 {
+                panic!("MAnaged object reference must have exactly 3 generic arguments!");
             }
             let assembly: Box<str> = garg_to_string(&subst[0], ctx).into();
             let assembly = Some(assembly).filter(|assembly| !assembly.is_empty());
@@ -512,7 +518,7 @@ fn magic_type<'tyctx>(
             let dotnet_tpe = DotnetTypeRef {
                 assembly: assembly,
                 name_path: name,
-                generics: vec![],
+                generics: managed_generics(&subst[2], ctx, method),
                 is_valuetype: false,
             };
             println!("dotnet_tpe:{dotnet_tpe:?}");
@@ -548,7 +554,65 @@ fn magic_type<'tyctx>(
                 .into(),
             )
         }
+        INTEROP_SPAN_TPE_NAME => {
+            if subst.len() != 2 {
+                panic!("Managed span must have exactly 2 generic arguments!");
+            }
+            let element = subst[0]
+                .as_type()
+                .expect("Span element type must be specified!");
+            let element = Type::from_ty(element, ctx, method);
+            let name = if crate::utilis::garag_to_bool(&subst[1], ctx) {
+                "System.ReadOnlySpan`1"
+            } else {
+                "System.Span`1"
+            };
+            let mut span = DotnetTypeRef::new(Some("System.Runtime"), name);
+            span.set_generics([element]);
+            Type::DotnetType(span.into())
+        }
+        INTEROP_DELEGATE_TPE_NAME => {
+            if subst.len() != 2 {
+                panic!("Managed delegate must have exactly 2 generic arguments!");
+            }
+            let args = subst[0]
+                .as_type()
+                .expect("Delegate argument types must be specified!");
+            let TyKind::Tuple(args) = args.kind() else {
+                panic!("Delegate arguments must be a tuple, but they are {args:?}!");
+            };
+            let mut generics: Vec<Type> = args
+                .iter()
+                .map(|arg| Type::from_ty(arg, ctx, method))
+                .collect();
+            let ret = subst[1]
+                .as_type()
+                .expect("Delegate return type must be specified!");
+            let ret = Type::from_ty(ret, ctx, method);
+            // Delegates returning nothing are `Action`s, and all others are `Func`s, whose last generic argument is the return type.
+            let name = if ret == Type::Void {
+                if generics.is_empty() {
+                    "System.Action".into()
+                } else {
+                    format!("System.Action`{}", generics.len())
+                }
+            } else {
+                generics.push(ret);
+                format!("System.Func`{}", generics.len())
+            };
+            let mut delegate = DotnetTypeRef::new(Some("System.Runtime"), &name);
+            delegate.set_generics(generics);
+            delegate.set_valuetype(false);
+            Type::DotnetType(delegate.into())
+        }
         INTEROP_CHR_TPE_NAME => Type::DotnetChar,
+        // Outside of managed method signatures, generic arguments are just values of their type.
+        INTEROP_GENERIC_ARG_TPE_NAME => {
+            let value = subst[0]
+                .as_type()
+                .expect("Type of a generic argument must be specified!");
+            Type::from_ty(value, ctx, method)
+        }
         _ => todo!("Interop type {name:?} is not yet supported!"),
     }
 }
@@ -574,6 +638,38 @@ fn garag_to_usize<'tyctx>(garg: &GenericArg<'tyctx>, _ctx: TyCtxt<'tyctx>) -> u6
         }
     }
 }
+/// Converts `generics`, a tuple of generic arguments of a managed type(`(K, V)` for `Dictionary<K, V>`), into .NET types.
+pub(crate) fn managed_generics<'tyctx>(
+    generics: &GenericArg<'tyctx>,
+    ctx: TyCtxt<'tyctx>,
+    method: &Instance<'tyctx>,
+) -> Vec<Type> {
+    let generics = generics
+        .as_type()
+        .expect("Generic arguments of a managed type must be a type!");
+    let generics = crate::utilis::monomorphize(method, generics, ctx);
+    let TyKind::Tuple(generics) = generics.kind() else {
+        panic!("Generic arguments of a managed type must be a tuple, but they are {generics:?}!");
+    };
+    generics
+        .iter()
+        .map(|generic| Type::from_ty(generic, ctx, method))
+        .collect()
+}
+/// Returns the type of `ty` in the signature of a managed method. Values wrapped in `RustcCLRInteropManagedGenericArg<T, INDEX>` are
+/// declared as generic parameter `INDEX` of the class owning the method(`!INDEX`), instead of `T`.
+pub(crate) fn managed_sig_type<'tyctx>(
+    ty: Ty<'tyctx>,
+    ctx: TyCtxt<'tyctx>,
+    method: &Instance<'tyctx>,
+) -> Type {
+    if let TyKind::Adt(adt_def, subst) = ty.kind() {
+        if crate::utilis::adt_name(adt_def).ends_with(INTEROP_GENERIC_ARG_TPE_NAME) {
+            return Type::GenericArg(garag_to_usize(&subst[1], ctx) as u32);
+        }
+    }
+    Type::from_ty(ty, ctx, method)
+}
 /// Creates a tuple with no more than 8 elements.
 pub fn simple_tuple(elements: &[Type]) -> DotnetTypeRef {
     assert!(elements.len() <= 8,"Tuple ({elements:?}) contains more than 8 elements, so it can't be stored inside a simple tuple.");
@@ -19,6 +19,14 @@ pub struct TypeDef {
     explicit_offsets: Option<Vec<u32>>,
     gargc: u32,
     extends: Option<DotnetTypeRef>,
+    /// Fields which are not `pub` in Rust, and should only be accessible within the assembly.
+    internal_fields: Vec<IString>,
+    /// Properties, as (name, type, getter method name) triples.
+    properties: Vec<(IString, Type, IString)>,
+    /// Managed interfaces implemented by this type.
+    implements: Vec<DotnetTypeRef>,
+    /// Set for static classes(`abstract sealed`), which can't be instantiated.
+    is_static: bool,
 }
 impl TypeDef {
     pub fn set_generic_count(&mut self, generic_count: u32) {
@@ -36,12 +44,49 @@ impl TypeDef {
     pub fn extends(&self) -> Option<&DotnetTypeRef> {
         self.extends.as_ref()
     }
+    pub fn set_extends(&mut self, extends: DotnetTypeRef) {
+        self.extends = Some(extends);
+    }
     pub fn fields(&self) -> &[(IString, Type)] {
         &self.fields
     }
     pub fn add_field(&mut self, name: IString, tpe: Type) {
         self.fields.push((name, tpe));
     }
+    /// Adds a field which is only accessible within the assembly.
+    pub fn add_internal_field(&mut self, name: IString, tpe: Type) {
+        self.internal_fields.push(name.clone());
+        self.fields.push((name, tpe));
+    }
+    /// Checks if field `name` should only be accessible within the assembly.
+    pub fn is_field_internal(&self, name: &str) -> bool {
+        self.internal_fields
+            .iter()
+            .any(|field| field.as_ref() == name)
+    }
+    pub fn properties(&self) -> &[(IString, Type, IString)] {
+        &self.properties
+    }
+    /// Adds a property named `name`, of type `tpe`, whose value is returned by method `getter` of this type.
+    pub fn add_property(&mut self, name: IString, tpe: Type, getter: IString) {
+        self.properties.push((name, tpe, getter));
+    }
+    pub fn implements(&self) -> &[DotnetTypeRef] {
+        &self.implements
+    }
+    /// Marks this type as implementing managed interface `interface`.
+    pub fn add_implements(&mut self, interface: DotnetTypeRef) {
+        if !self.implements.contains(&interface) {
+            self.implements.push(interface);
+        }
+    }
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+    /// Marks this type as a static class, which can't be instantiated and only has static methods.
+    pub fn set_static(&mut self) {
+        self.is_static = true;
+    }
     pub fn inner_types(&self) -> &[Self] {
         &self.inner_types
     }
@@ -64,6 +109,10 @@ impl TypeDef {
             gargc: 0,
             extends: None,
             explicit_offsets: None,
+            internal_fields: vec![],
+            properties: vec![],
+            implements: vec![],
+            is_static: false,
         }
     }
     pub fn from_ty<'tyctx>(
@@ -111,6 +160,7 @@ impl TypeDef {
         let access = AccessModifer::Public;
         let mut fields = Vec::with_capacity(adt_def.all_fields().count());
         let mut res = Vec::new();
+        let mut internal_fields = Vec::new();
         adt_def.all_fields().for_each(|field| {
             let resolved_field_ty = field.ty(ctx, subst);
             //This is a simple loop prevention. More complex types may still lead to cycles. TODO: deal with cycles.
@@ -137,6 +187,9 @@ impl TypeDef {
                 Type::generic_from_ty(generic_ty, ctx)
             };
             let name = escape_field_name(&field.name.to_string());
+            if !field.vis.is_public() {
+                internal_fields.push(name.clone());
+            }
             fields.push((name, ty));
         }
         res.push(Self {
@@ -148,6 +201,10 @@ impl TypeDef {
             gargc,
             extends: None,
             explicit_offsets: None,
+            internal_fields,
+            properties: vec![],
+            implements: vec![],
+            is_static: false,
         });
         res
     }
@@ -163,6 +220,7 @@ impl TypeDef {
         let access = AccessModifer::Public;
         let mut fields = Vec::with_capacity(adt_def.all_fields().count());
         let mut res = Vec::new();
+        let mut internal_fields = Vec::new();
         adt_def.all_fields().for_each(|field| {
             let resolved_field_ty = field.ty(ctx, subst);
             let resolved_field_ty = monomorphize(method, resolved_field_ty, ctx);
@@ -180,6 +238,9 @@ impl TypeDef {
             }
             let ty = Type::generic_from_ty(generic_ty, ctx);
             let name = escape_field_name(&field.name.to_string());
+            if !field.vis.is_public() {
+                internal_fields.push(name.clone());
+            }
             fields.push((name, ty));
         }
         let explicit_offsets = Some(fields.iter().map(|_| 0).collect());
@@ -192,6 +253,10 @@ impl TypeDef {
             gargc,
             extends: None,
             explicit_offsets,
+            internal_fields,
+            properties: vec![],
+            implements: vec![],
+            is_static: false,
         });
         res
     }
@@ -266,6 +331,10 @@ impl TypeDef {
                 gargc,
                 extends: None,
                 explicit_offsets: None,
+                internal_fields: vec![],
+                properties: vec![],
+                implements: vec![],
+                is_static: false,
             });
         }
         res.push(Self {
@@ -277,6 +346,10 @@ impl TypeDef {
             gargc,
             extends: None,
             explicit_offsets: Some(explicit_offsets),
+            internal_fields: vec![],
+            properties: vec![],
+            implements: vec![],
+            is_static: false,
         });
         res
     }
@@ -338,6 +411,10 @@ pub fn get_array_type(element_count: usize) -> TypeDef {
         explicit_offsets: None,
         gargc: 1,
         extends: None,
+        internal_fields: vec![],
+        properties: vec![],
+        implements: vec![],
+        is_static: false,
     };
     // set_Item(usize offset, G0 value)
     let mut set_usize = Method::new(
@@ -413,3 +490,118 @@ pub fn get_array_type(element_count: usize) -> TypeDef {
     def.add_method(get_item_usize);
     def
 }
+/// The exception thrown by Rust panics. It is distinct from all other exceptions, so that code catching managed exceptions can let it pass.
+pub fn rust_panic_type() -> TypeDef {
+    use crate::cil_op::{CILOp, CallSite};
+    let exception = DotnetTypeRef::new(Some("System.Runtime"), "System.Exception");
+    let mut def = TypeDef {
+        access: AccessModifer::Public,
+        name: crate::utilis::RUST_PANIC_CLASS_NAME.into(),
+        inner_types: vec![],
+        fields: vec![],
+        functions: vec![],
+        explicit_offsets: None,
+        gargc: 0,
+        extends: Some(exception.clone()),
+        internal_fields: vec![],
+        properties: vec![],
+        implements: vec![],
+        is_static: false,
+    };
+    let mut ctor = Method::new(
+        AccessModifer::Public,
+        false,
+        crate::function_sig::FnSig::new(&[crate::utilis::rust_panic_class().into()], &Type::Void),
+        ".ctor",
+        vec![],
+    );
+    ctor.set_ops(vec![
+        CILOp::LDArg(0),
+        CILOp::Call(CallSite::boxed(
+            Some(exception.clone()),
+            ".ctor".into(),
+            crate::function_sig::FnSig::new(&[exception.into()], &Type::Void),
+            false,
+        )),
+        CILOp::Ret,
+    ]);
+    def.add_method(ctor);
+    def
+}
+/// The class carrying the environment of a Rust closure called by a delegate. It holds a pointer to the environment, and a pointer to the
+/// function dropping and freeing it, which is called when the carrier is finalized.
+pub fn closure_env_type() -> TypeDef {
+    use crate::cil_op::{CILOp, CallSite, FieldDescriptor};
+    use crate::function_sig::FnSig;
+    let class = crate::utilis::closure_env_class();
+    let object = DotnetTypeRef::new(Some("System.Runtime"), "System.Object");
+    let mut def = TypeDef {
+        access: AccessModifer::Public,
+        name: crate::utilis::CLOSURE_ENV_CLASS_NAME.into(),
+        inner_types: vec![],
+        fields: vec![("env".into(), Type::ISize), ("drop_fn".into(), Type::ISize)],
+        functions: vec![],
+        explicit_offsets: None,
+        gargc: 0,
+        extends: Some(object.clone()),
+        internal_fields: vec![],
+        properties: vec![],
+        implements: vec![],
+        is_static: false,
+    };
+    let field = |name: &str| FieldDescriptor::boxed(class.clone(), Type::ISize, name.into());
+    // .ctor(native int env, native int drop_fn)
+    let mut ctor = Method::new(
+        AccessModifer::Public,
+        false,
+        FnSig::new(
+            &[class.clone().into(), Type::ISize, Type::ISize],
+            &Type::Void,
+        ),
+        ".ctor",
+        vec![],
+    );
+    ctor.set_ops(vec![
+        CILOp::LDArg(0),
+        CILOp::Call(CallSite::boxed(
+            Some(object.clone()),
+            ".ctor".into(),
+            FnSig::new(&[object.clone().into()], &Type::Void),
+            false,
+        )),
+        CILOp::LDArg(0),
+        CILOp::LDArg(1),
+        CILOp::STField(field("env")),
+        CILOp::LDArg(0),
+        CILOp::LDArg(2),
+        CILOp::STField(field("drop_fn")),
+        CILOp::Ret,
+    ]);
+    def.add_method(ctor);
+    // Overrides `System.Object.Finalize`, calling `drop_fn(env)` and then the base finalizer.
+    let mut finalize = Method::new(
+        AccessModifer::Family,
+        false,
+        FnSig::new(&[class.clone().into()], &Type::Void),
+        "Finalize",
+        vec![],
+    );
+    finalize.add_attribute(crate::method::Attribute::Virtual);
+    finalize.set_ops(vec![
+        CILOp::LDArg(0),
+        CILOp::LDField(field("env")),
+        CILOp::LDArg(0),
+        CILOp::LDField(field("drop_fn")),
+        CILOp::CallI(Box::new(FnSig::new(&[Type::ISize], &Type::Void))),
+        CILOp::LDArg(0),
+        CILOp::Call(CallSite::boxed(
+            Some(object.clone()),
+            "Finalize".into(),
+            FnSig::new(&[object.into()], &Type::Void),
+            false,
+        )),
+        CILOp::Ret,
+    ]);
+    def.add_method(finalize);
+    def
+}
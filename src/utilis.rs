@@ -2,15 +2,87 @@ use rustc_middle::ty::{
     AdtDef, AliasKind, Binder, BoundVariableKind, Const, ConstKind, EarlyBinder, GenericArg,
     Instance, ParamEnv, SymbolName, Ty, TyCtxt, TyKind, TypeFoldable,
 };
-pub const BEGIN_TRY: &str = "rustc_clr_interop_begin_try";
-pub const END_TRY: &str = "rustc_clr_interop_end_try";
-pub const BEGIN_CATCH: &str = "rustc_clr_interop_begin_catch";
-pub const END_CATCH: &str = "rustc_clr_interop_end_catch";
+pub const TRY_CATCH_FN_NAME: &str = "rustc_clr_interop_try_catch_";
 pub const CTOR_FN_NAME: &str = "rustc_clr_interop_managed_ctor";
 pub const MANAGED_CALL_FN_NAME: &str = "rustc_clr_interop_managed_call";
 pub const MANAGED_CALL_VIRT_FN_NAME: &str = "rustc_clr_interop_managed_call_virt";
+pub const LD_FIELD_FN_NAME: &str = "rustc_clr_interop_managed_ld_field_";
+pub const ST_FIELD_FN_NAME: &str = "rustc_clr_interop_managed_st_field_";
+pub const LD_STATIC_FIELD_FN_NAME: &str = "rustc_clr_interop_managed_ld_static_field_";
+pub const ST_STATIC_FIELD_FN_NAME: &str = "rustc_clr_interop_managed_st_static_field_";
+pub const GET_PROPERTY_FN_NAME: &str = "rustc_clr_interop_managed_get_property_";
+pub const SET_PROPERTY_FN_NAME: &str = "rustc_clr_interop_managed_set_property_";
+pub const GET_STATIC_PROPERTY_FN_NAME: &str = "rustc_clr_interop_managed_get_static_property_";
+pub const SET_STATIC_PROPERTY_FN_NAME: &str = "rustc_clr_interop_managed_set_static_property_";
+pub const GC_HANDLE_ALLOC_FN_NAME: &str = "rustc_clr_interop_gc_handle_alloc_";
+pub const GC_HANDLE_TARGET_FN_NAME: &str = "rustc_clr_interop_gc_handle_target_";
+pub const GC_HANDLE_FREE_FN_NAME: &str = "rustc_clr_interop_gc_handle_free_";
+pub const GC_HANDLE_PIN_FN_NAME: &str = "rustc_clr_interop_gc_handle_pin_";
+/// Interop intrinsics managing `GCHandle`s.
+pub const GC_HANDLE_FN_NAMES: [&str; 4] = [
+    GC_HANDLE_ALLOC_FN_NAME,
+    GC_HANDLE_TARGET_FN_NAME,
+    GC_HANDLE_FREE_FN_NAME,
+    GC_HANDLE_PIN_FN_NAME,
+];
+pub const MANAGED_ARRAY_NEW_FN_NAME: &str = "rustc_clr_interop_managed_array_new_";
+pub const MANAGED_ARRAY_LEN_FN_NAME: &str = "rustc_clr_interop_managed_array_len_";
+pub const MANAGED_ARRAY_GET_FN_NAME: &str = "rustc_clr_interop_managed_array_get_";
+pub const MANAGED_ARRAY_SET_FN_NAME: &str = "rustc_clr_interop_managed_array_set_";
+pub const MANAGED_ARRAY_ADDRESS_FN_NAME: &str = "rustc_clr_interop_managed_array_address_";
+pub const MANAGED_SPAN_NEW_FN_NAME: &str = "rustc_clr_interop_managed_span_new_";
+pub const DELEGATE_FROM_FN_PTR_FN_NAME: &str = "rustc_clr_interop_delegate_from_fn_ptr_";
+pub const DELEGATE_NEW_FN_NAME: &str = "rustc_clr_interop_delegate_new_";
+pub const DELEGATE_ENV_FN_NAME: &str = "rustc_clr_interop_delegate_env_";
+pub const GENERIC_ARG_WRAP_FN_NAME: &str = "rustc_clr_interop_managed_generic_arg_wrap_";
+pub const GENERIC_ARG_UNWRAP_FN_NAME: &str = "rustc_clr_interop_managed_generic_arg_unwrap_";
+/// Interop intrinsics converting between values and generic arguments of managed classes, which are represented the same way.
+pub const GENERIC_ARG_FN_NAMES: [&str; 2] = [GENERIC_ARG_WRAP_FN_NAME, GENERIC_ARG_UNWRAP_FN_NAME];
+/// Interop intrinsics creating delegates from Rust functions.
+pub const DELEGATE_FN_NAMES: [&str; 3] = [
+    DELEGATE_FROM_FN_PTR_FN_NAME,
+    DELEGATE_NEW_FN_NAME,
+    DELEGATE_ENV_FN_NAME,
+];
+/// Interop intrinsics creating and accessing managed arrays.
+pub const MANAGED_ARRAY_FN_NAMES: [&str; 5] = [
+    MANAGED_ARRAY_NEW_FN_NAME,
+    MANAGED_ARRAY_LEN_FN_NAME,
+    MANAGED_ARRAY_GET_FN_NAME,
+    MANAGED_ARRAY_SET_FN_NAME,
+    MANAGED_ARRAY_ADDRESS_FN_NAME,
+];
+/// Interop intrinsics accessing fields and properties of managed types.
+pub const MANAGED_MEMBER_FN_NAMES: [&str; 8] = [
+    LD_FIELD_FN_NAME,
+    ST_FIELD_FN_NAME,
+    LD_STATIC_FIELD_FN_NAME,
+    ST_STATIC_FIELD_FN_NAME,
+    GET_PROPERTY_FN_NAME,
+    SET_PROPERTY_FN_NAME,
+    GET_STATIC_PROPERTY_FN_NAME,
+    SET_STATIC_PROPERTY_FN_NAME,
+];
 pub fn is_function_magic(name: &str) -> bool {
-    name.contains(CTOR_FN_NAME) || name.contains(MANAGED_CALL_FN_NAME)
+    name.contains(CTOR_FN_NAME)
+        || name.contains(MANAGED_CALL_FN_NAME)
+        || MANAGED_MEMBER_FN_NAMES
+            .iter()
+            .any(|member_fn| name.contains(member_fn))
+        || GC_HANDLE_FN_NAMES
+            .iter()
+            .any(|handle_fn| name.contains(handle_fn))
+        || MANAGED_ARRAY_FN_NAMES
+            .iter()
+            .any(|array_fn| name.contains(array_fn))
+        || name.contains(MANAGED_SPAN_NEW_FN_NAME)
+        || name.contains(TRY_CATCH_FN_NAME)
+        || DELEGATE_FN_NAMES
+            .iter()
+            .any(|delegate_fn| name.contains(delegate_fn))
+        || GENERIC_ARG_FN_NAMES
+            .iter()
+            .any(|generic_arg_fn| name.contains(generic_arg_fn))
 }
 use crate::{codegen_error::MethodCodegenError, r#type::DotnetTypeRef};
 pub fn skip_binder_if_no_generic_types<T>(binder: Binder<T>) -> Result<T, MethodCodegenError> {
@@ -209,6 +281,23 @@ pub fn string_class() -> DotnetTypeRef {
     string.set_valuetype(false);
     string
 }
+/// Name of the exception class thrown by Rust panics.
+pub const RUST_PANIC_CLASS_NAME: &str = "RustPanic";
+/// The exception class thrown by Rust panics, defined in every assembly.
+pub fn rust_panic_class() -> DotnetTypeRef {
+    let mut panic = DotnetTypeRef::new(None, RUST_PANIC_CLASS_NAME);
+    panic.set_valuetype(false);
+    panic
+}
+/// Name of the class carrying the environment of a Rust closure called by a delegate.
+pub const CLOSURE_ENV_CLASS_NAME: &str = "RustClosureEnv";
+/// The class carrying the environment of a Rust closure called by a delegate, defined in every assembly. The environment is dropped when
+/// the carrier is finalized.
+pub fn closure_env_class() -> DotnetTypeRef {
+    let mut env = DotnetTypeRef::new(None, CLOSURE_ENV_CLASS_NAME);
+    env.set_valuetype(false);
+    env
+}
 pub fn usize_class() -> DotnetTypeRef {
     let mut string = DotnetTypeRef::new(Some("System.Runtime"), "System.UIntPtr");
     //TODO: Inwestigate this. The valuetype prefix seems to be missing from UIntPtr in compiled C# code
@@ -0,0 +1,157 @@
+use crate::{
+    assembly::Assembly,
+    exports::{instance_wrapper, pascal_case, SelfKind},
+    function_sig::FnSig,
+    method::{Attribute, Method},
+    r#type::{DotnetTypeRef, Type},
+};
+use rustc_hir::def::DefKind;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::{
+    EarlyBinder, Instance, InstanceDef, ParamEnv, TraitRef, Ty, TyCtxt, TyKind,
+};
+/// Name of the mycorrhiza marker trait. A trait having it as a supertrait declares its methods as virtual methods of a managed type.
+const VIRTUALS_TRAIT_NAME: &str = "RustcCLRInteropVirtuals";
+/// Adds virtual methods forwarding to methods of trait impls, if the trait declares its methods as virtual methods of a managed interface or
+/// base type. Such methods are named like the Rust ones, in PascalCase, and the CLR matches them with the methods of the interface or base
+/// type by name and signature. Types implementing an interface this way get that interface added to their interface list.
+pub fn add_virtuals<'tcx>(
+    asm: &mut Assembly,
+    tcx: TyCtxt<'tcx>,
+    items: impl Iterator<Item = &'tcx MonoItem<'tcx>>,
+) {
+    let mut virtuals: Vec<VirtualMethod> = items
+        .filter_map(|item| match item {
+            MonoItem::Fn(instance) => virtual_method(tcx, item, instance),
+            _ => None,
+        })
+        .collect();
+    // Mono items are not ordered, so methods are sorted to keep the output deterministic.
+    virtuals.sort_by(|a, b| (&a.type_name, a.method.name()).cmp(&(&b.type_name, b.method.name())));
+    for virt in virtuals {
+        // The type may not have been used by any function, so it is added, in case it is missing.
+        asm.add_type(virt.self_ty, tcx, &virt.instance);
+        let Some(mut type_def) = asm.take_typedef(&virt.type_name) else {
+            panic!(
+                "Can't add the virtual method {} to {}, since the assembly contains no definition of that type!",
+                virt.method.name(),
+                virt.type_name
+            );
+        };
+        if let Some(interface) = virt.interface {
+            type_def.add_implements(interface);
+        }
+        type_def.add_method(virt.method);
+        asm.add_typedef(type_def);
+    }
+}
+/// A virtual method forwarding to a method of a trait impl.
+struct VirtualMethod<'tcx> {
+    /// The Rust type implementing the trait.
+    self_ty: Ty<'tcx>,
+    /// Name of the type definition of `self_ty`.
+    type_name: String,
+    /// The interface the method belongs to, if it does not override a method of `System.Object`.
+    interface: Option<DotnetTypeRef>,
+    method: Method,
+    /// The method of the trait impl.
+    instance: Instance<'tcx>,
+}
+/// If `instance` is a method of a non-generic trait impl for an ADT, and the trait declares its methods as virtual, returns the virtual
+/// method forwarding to `instance`.
+fn virtual_method<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    item: &MonoItem<'tcx>,
+    instance: &Instance<'tcx>,
+) -> Option<VirtualMethod<'tcx>> {
+    if !matches!(instance.def, InstanceDef::Item(_)) || !instance.args.is_empty() {
+        return None;
+    }
+    let def_id = instance.def_id();
+    if tcx.def_kind(def_id) != DefKind::AssocFn
+        || !tcx.associated_item(def_id).fn_has_self_parameter
+    {
+        return None;
+    }
+    let impl_id = tcx.parent(def_id);
+    if tcx.def_kind(impl_id) != (DefKind::Impl { of_trait: true }) {
+        return None;
+    }
+    let trait_ref = tcx.impl_trait_ref(impl_id)?.instantiate_identity();
+    let TyKind::Adt(adt_def, adt_args) = trait_ref.self_ty().kind() else {
+        return None;
+    };
+    if !adt_args.is_empty() {
+        return None;
+    }
+    let (base, is_interface) = managed_base(tcx, trait_ref, instance)?;
+    // Rust types are value types, which can't have base types other than `System.ValueType`. Their virtual methods can only
+    // override the ones `System.ValueType` inherits from `System.Object`.
+    if !is_interface && !matches!(base.name_path(), "System.Object" | "System.ValueType") {
+        tcx.dcx().span_fatal(
+            tcx.def_span(impl_id),
+            format!(
+                "`{}` overrides methods of the class `{}`, but Rust types are value types, which can only override virtual methods of `System.Object`",
+                tcx.def_path_str(trait_ref.def_id),
+                base.name_path()
+            ),
+        );
+    }
+    let type_name = crate::utilis::adt_name(adt_def).to_string();
+    let poly_sig = instance.ty(tcx, ParamEnv::reveal_all()).fn_sig(tcx);
+    let sig = FnSig::from_poly_sig(&poly_sig, tcx, instance)
+        .expect("Could not get the signature of a virtual method!");
+    let self_kind = match poly_sig.skip_binder().inputs()[0].kind() {
+        TyKind::Ref(_, _, mutability) => SelfKind::Ref(mutability.is_mut()),
+        _ => SelfKind::Value,
+    };
+    let self_tpe = Type::DotnetType(Box::new(DotnetTypeRef::new(None, &type_name)));
+    let symbol = crate::utilis::function_name(item.symbol_name(tcx));
+    let name = pascal_case(tcx.item_name(def_id).as_str());
+    let mut method = instance_wrapper(&name, &symbol, sig, self_kind, self_tpe);
+    method.add_attribute(Attribute::Virtual);
+    Some(VirtualMethod {
+        self_ty: trait_ref.self_ty(),
+        type_name,
+        interface: is_interface.then_some(base),
+        method,
+        instance: *instance,
+    })
+}
+/// Looks for the `RustcCLRInteropVirtuals<ASSEMBLY, TYPE_PATH, IS_INTERFACE, Generics>` supertrait of the trait of `trait_ref`, and returns
+/// the managed type it refers to, and whether that type is an interface.
+fn managed_base<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_ref: TraitRef<'tcx>,
+    instance: &Instance<'tcx>,
+) -> Option<(DotnetTypeRef, bool)> {
+    tcx.super_predicates_of(trait_ref.def_id)
+        .predicates
+        .iter()
+        .find_map(|(clause, _)| {
+            let clause = EarlyBinder::bind(*clause).instantiate(tcx, trait_ref.args);
+            let marker = clause.as_trait_clause()?.skip_binder().trait_ref;
+            if tcx.item_name(marker.def_id).as_str() != VIRTUALS_TRAIT_NAME {
+                return None;
+            }
+            // The marker is recognized by name, so a user-defined trait named like it may have other generic arguments.
+            if marker.args.len() != 5 {
+                tcx.dcx().span_fatal(
+                    tcx.def_span(trait_ref.def_id),
+                    format!(
+                        "`{}` is not a valid {VIRTUALS_TRAIT_NAME} marker: it must have the `ASSEMBLY`, `TYPE_PATH`, `IS_INTERFACE` and `Generics` generic arguments",
+                        tcx.def_path_str(marker.def_id)
+                    ),
+                );
+            }
+            let asm = crate::utilis::garg_to_string(&marker.args[1], tcx);
+            let asm = Some(asm).filter(|asm| !asm.is_empty());
+            let type_path = crate::utilis::garg_to_string(&marker.args[2], tcx);
+            let is_interface = crate::utilis::garag_to_bool(&marker.args[3], tcx);
+            let generics = crate::r#type::managed_generics(&marker.args[4], tcx, instance);
+            let mut base = DotnetTypeRef::new(asm.as_deref(), &type_path);
+            base.set_generics(generics);
+            base.set_valuetype(false);
+            Some((base, is_interface))
+        })
+}
@@ -6,7 +6,7 @@ fn main(){
     let mut i = black_box(0);
     for _ in 0..1234{
         i+=1;
-        rustc_clr_interop_managed_call1_::<"System.Console","System.Console",false,"WriteLine",true,(),i32>(i);
+        rustc_clr_interop_managed_call1_::<"System.Console","System.Console",false,(),"WriteLine",true,(),i32>(i);
     }
     test_eq!(black_box(i),1234);
 }
@@ -0,0 +1,81 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[allow(dead_code)]
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
+    prevent_construction:usize,
+}
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedDelegate<Args,Ret>{
+    pd:core::marker::PhantomData<(Args,Ret)>,
+    prevent_construction:usize,
+}
+#[derive(Clone,Copy)]
+#[repr(transparent)]
+struct RustcCLRInteropManagedGenericArg<T,const INDEX:usize>{
+    value:T,
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_generic_arg_wrap_<T,const INDEX:usize>(value:T)->RustcCLRInteropManagedGenericArg<T,INDEX>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_generic_arg_unwrap_<T,const INDEX:usize>(arg:RustcCLRInteropManagedGenericArg<T,INDEX>)->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_delegate_from_fn_ptr_<Args,Ret,FnPtr:Copy>(fn_ptr:FnPtr)->RustcCLRInteropManagedDelegate<Args,Ret>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_delegate_new_<Args,Ret,FnPtr:Copy>(env:*const (),drop_env:unsafe fn(*mut ()),thunk:FnPtr)->RustcCLRInteropManagedDelegate<Args,Ret>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_delegate_env_(target:RustcCLRInteropManagedClass<"System.Runtime","System.Object">)->*const (){
+    core::intrinsics::abort();
+}
+extern "C"{
+    fn free(ptr:*mut ());
+}
+type Object = RustcCLRInteropManagedClass<"System.Runtime","System.Object">;
+type Func = RustcCLRInteropManagedDelegate<(i32,),i32>;
+type Arg = RustcCLRInteropManagedGenericArg<i32,0>;
+type Ret = RustcCLRInteropManagedGenericArg<i32,1>;
+/// Calls `func` from managed code, using `Func<int,int>.Invoke`.
+fn invoke(func:Func,arg:i32)->i32{
+    let ret = rustc_clr_interop_managed_call2_::<"System.Runtime","System.Func`2",false,(i32,i32),"Invoke",false,Ret,Func,Arg>(func,rustc_clr_interop_managed_generic_arg_wrap_::<i32,0>(arg));
+    rustc_clr_interop_managed_generic_arg_unwrap_::<i32,1>(ret)
+}
+fn double(value:i32)->i32{
+    value * 2
+}
+struct Offset{
+    offset:i32,
+}
+/// Adds the offset captured in the environment of the delegate to `value`.
+fn offset_thunk(target:Object,value:i32)->i32{
+    let env = rustc_clr_interop_delegate_env_(target) as *const Offset;
+    unsafe{(*env).offset + value}
+}
+unsafe fn drop_offset(env:*mut ()){
+    free(env);
+}
+fn main(){
+    let from_fn = rustc_clr_interop_delegate_from_fn_ptr_::<(i32,),i32,_>(double as fn(i32)->i32);
+    test_eq!(invoke(from_fn,21),42);
+    let env = unsafe{malloc(core::mem::size_of::<Offset>())} as *mut Offset;
+    unsafe{env.write(Offset{offset:100})};
+    let thunk:fn(Object,i32)->i32 = offset_thunk;
+    let closure = rustc_clr_interop_delegate_new_::<(i32,),i32,_>(env as *const (),drop_offset,thunk);
+    test_eq!(invoke(closure,5),105);
+    test_eq!(invoke(closure,-100),0);
+}
@@ -0,0 +1,57 @@
+#![feature(lang_items,adt_const_params,core_intrinsics)]
+#![allow(internal_features,incomplete_features,dead_code)]
+#![no_std]
+// A library type with exported methods, which also implements virtual methods of an interface and of `System.Object`.
+// All of them must end up in a single definition of the type.
+pub trait RustcCLRInteropVirtuals<const ASSEMBLY:&'static str,const TYPE_PATH:&'static str,const IS_INTERFACE:bool,Generics = ()>{}
+impl<T:?Sized,const ASSEMBLY:&'static str,const TYPE_PATH:&'static str,const IS_INTERFACE:bool,Generics> RustcCLRInteropVirtuals<ASSEMBLY,TYPE_PATH,IS_INTERFACE,Generics> for T{}
+pub trait IComparable<T>:RustcCLRInteropVirtuals<"System.Runtime","System.IComparable`1",true,(T,)>{
+    fn compare_to(&self,other:T)->i32;
+}
+pub trait ObjectVirtuals:RustcCLRInteropVirtuals<"System.Runtime","System.Object",false>{
+    fn get_hash_code(&self)->i32;
+}
+#[derive(Clone,Copy)]
+pub struct Priority{
+    pub value:i32,
+    boost:i32,
+}
+impl Priority{
+    pub fn new(value:i32)->Self{
+        Self{value,boost:0}
+    }
+    pub fn value(&self)->i32{
+        self.value + self.boost
+    }
+    pub fn boost(&mut self,by:i32){
+        self.boost += by;
+    }
+}
+impl IComparable<Priority> for Priority{
+    fn compare_to(&self,other:Priority)->i32{
+        other.value() - self.value()
+    }
+}
+impl ObjectVirtuals for Priority{
+    fn get_hash_code(&self)->i32{
+        self.value()
+    }
+}
+// `add` and `div` are also CIL instructions, so their names must be quoted in the IL.
+pub fn add(a:i32,b:i32)->i32{
+    a + b
+}
+pub fn div(a:i32,b:i32)->i32{
+    if b == 0{
+        0
+    }
+    else{
+        a / b
+    }
+}
+#[lang = "eh_personality"]
+fn rust_eh_personality() {}
+#[panic_handler]
+fn panic(_panic:&core::panic::PanicInfo<'_>)->!{
+    core::intrinsics::abort();
+}
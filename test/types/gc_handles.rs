@@ -0,0 +1,51 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[allow(dead_code)]
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
+    prevent_construction:usize,
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ctor1_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,Arg1>(arg1:Arg1)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_gc_handle_alloc_<T>(obj:T)->isize{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_gc_handle_target_<T>(handle:isize)->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_gc_handle_free_(handle:isize){
+    core::intrinsics::abort();
+}
+type StringBuilder = RustcCLRInteropManagedClass<"System.Runtime","System.Text.StringBuilder">;
+/// Rust data living in unmanaged memory, which refers to a managed object.
+struct Holder{
+    tag:u32,
+    builder:isize,
+}
+fn main(){
+    let holder = unsafe{malloc(core::mem::size_of::<Holder>())} as *mut Holder;
+    let builder = rustc_clr_interop_managed_ctor1_::<"System.Runtime","System.Text.StringBuilder",false,(),i32>(16);
+    unsafe{
+        holder.write(Holder{tag:0xC0FFEE,builder:rustc_clr_interop_gc_handle_alloc_::<StringBuilder>(builder)});
+    }
+    // The handle must keep the object alive, and track it if it gets moved.
+    rustc_clr_interop_managed_call0_::<"System.Runtime","System.GC",false,(),"Collect",()>();
+    let (tag,handle) = unsafe{((*holder).tag,(*holder).builder)};
+    test_eq!(tag,0xC0FFEE);
+    let builder = rustc_clr_interop_gc_handle_target_::<StringBuilder>(handle);
+    let capacity = rustc_clr_interop_managed_call1_::<"System.Runtime","System.Text.StringBuilder",false,(),"get_Capacity",false,i32,StringBuilder>(builder);
+    test_eq!(capacity,16);
+    rustc_clr_interop_gc_handle_free_(handle);
+}
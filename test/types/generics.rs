@@ -0,0 +1,66 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[allow(dead_code)]
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
+    prevent_construction:usize,
+}
+#[derive(Clone,Copy)]
+#[repr(transparent)]
+struct RustcCLRInteropManagedGenericArg<T,const INDEX:usize>{
+    value:T,
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ctor0_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics>()->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_generic_arg_wrap_<T,const INDEX:usize>(value:T)->RustcCLRInteropManagedGenericArg<T,INDEX>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_generic_arg_unwrap_<T,const INDEX:usize>(arg:RustcCLRInteropManagedGenericArg<T,INDEX>)->T{
+    core::intrinsics::abort();
+}
+type List = RustcCLRInteropManagedClass<"System.Collections","System.Collections.Generic.List`1",(i32,)>;
+type Dictionary = RustcCLRInteropManagedClass<"System.Collections","System.Collections.Generic.Dictionary`2",(i32,i32)>;
+type Item = RustcCLRInteropManagedGenericArg<i32,0>;
+type Key = RustcCLRInteropManagedGenericArg<i32,0>;
+type Value = RustcCLRInteropManagedGenericArg<i32,1>;
+fn main(){
+    list();
+    dictionary();
+}
+fn list(){
+    let list = rustc_clr_interop_managed_ctor0_::<"System.Collections","System.Collections.Generic.List`1",false,(i32,)>();
+    for i in 0..8{
+        rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.List`1",false,(i32,),"Add",false,(),List,Item>(list,rustc_clr_interop_managed_generic_arg_wrap_::<i32,0>(i * 3));
+    }
+    let count = rustc_clr_interop_managed_call1_::<"System.Collections","System.Collections.Generic.List`1",false,(i32,),"get_Count",false,i32,List>(list);
+    test_eq!(count,8);
+    let item = rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.List`1",false,(i32,),"get_Item",false,Item,List,i32>(list,5);
+    test_eq!(rustc_clr_interop_managed_generic_arg_unwrap_::<i32,0>(item),15);
+    rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.List`1",false,(i32,),"RemoveAt",false,(),List,i32>(list,0);
+    let first = rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.List`1",false,(i32,),"get_Item",false,Item,List,i32>(list,0);
+    test_eq!(rustc_clr_interop_managed_generic_arg_unwrap_::<i32,0>(first),3);
+    let contains = rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.List`1",false,(i32,),"Contains",false,bool,List,Item>(list,rustc_clr_interop_managed_generic_arg_wrap_::<i32,0>(21));
+    test!(contains);
+}
+fn dictionary(){
+    let dict = rustc_clr_interop_managed_ctor0_::<"System.Collections","System.Collections.Generic.Dictionary`2",false,(i32,i32)>();
+    for i in 0..4{
+        rustc_clr_interop_managed_call3_::<"System.Collections","System.Collections.Generic.Dictionary`2",false,(i32,i32),"set_Item",false,(),Dictionary,Key,Value>(dict,rustc_clr_interop_managed_generic_arg_wrap_::<i32,0>(i),rustc_clr_interop_managed_generic_arg_wrap_::<i32,1>(i * i));
+    }
+    let count = rustc_clr_interop_managed_call1_::<"System.Collections","System.Collections.Generic.Dictionary`2",false,(i32,i32),"get_Count",false,i32,Dictionary>(dict);
+    test_eq!(count,4);
+    let value = rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.Dictionary`2",false,(i32,i32),"get_Item",false,Value,Dictionary,Key>(dict,rustc_clr_interop_managed_generic_arg_wrap_::<i32,0>(3));
+    test_eq!(rustc_clr_interop_managed_generic_arg_unwrap_::<i32,1>(value),9);
+    let contains = rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.Dictionary`2",false,(i32,i32),"ContainsKey",false,bool,Dictionary,Key>(dict,rustc_clr_interop_managed_generic_arg_wrap_::<i32,0>(7));
+    test!(!contains);
+}
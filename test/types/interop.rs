@@ -4,7 +4,8 @@
 include!("../common.rs");
 #[allow(dead_code)]
 #[derive(Clone,Copy)]
-struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str>{
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
     prevent_construction:usize,
 }
 #[derive(Clone,Copy)]
@@ -29,22 +30,22 @@ impl From<char> for RustcCLRInteropManagedChar{
 
 #[allow(dead_code)]
 #[inline(never)]
-fn rustc_clr_interop_managed_ctor0_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool>()->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH>{
+fn rustc_clr_interop_managed_ctor0_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics>()->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
     core::intrinsics::abort();
 }
 #[allow(dead_code)]
 #[inline(never)]
-fn rustc_clr_interop_managed_ctor1_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Arg1>(arg1:Arg1)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH>{
+fn rustc_clr_interop_managed_ctor1_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,Arg1>(arg1:Arg1)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
     core::intrinsics::abort();
 }
 #[allow(dead_code)]
 #[inline(never)]
-fn rustc_clr_interop_managed_ctor2_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Arg1,Arg2>(arg1:Arg1,arg2:Arg2)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH>{
+fn rustc_clr_interop_managed_ctor2_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,Arg1,Arg2>(arg1:Arg1,arg2:Arg2)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
     core::intrinsics::abort();
 }
 #[allow(dead_code)]
 #[inline(never)]
-fn rustc_clr_interop_managed_ctor3_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Arg1,Arg2,Arg3>(arg1:Arg1,arg2:Arg2,arg3:Arg3)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH>{
+fn rustc_clr_interop_managed_ctor3_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,Arg1,Arg2,Arg3>(arg1:Arg1,arg2:Arg2,arg3:Arg3)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
     core::intrinsics::abort();
 }
 //struct RustcCLRInteropManagedBool;
@@ -73,24 +74,24 @@ fn main(){
     new_helloworld();
 }
 fn test_ctors(){
-    let obj = black_box(rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.Object",false>());
+    let obj = black_box(rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.Object",false,()>());
     black_box(obj);
     //let strig = rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.String">();
     //black_box(strig);
-    let sb = rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.Text.StringBuilder",false>();
+    let sb = rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.Text.StringBuilder",false,()>();
     black_box(sb);
 
-    let vec3 = rustc_clr_interop_managed_ctor3_::<"System.Numerics.Vectors","System.Numerics.Vector3",true,f32,f32,f32>(0.0,1.5,4.6);
+    let vec3 = rustc_clr_interop_managed_ctor3_::<"System.Numerics.Vectors","System.Numerics.Vector3",true,(),f32,f32,f32>(0.0,1.5,4.6);
     black_box(vec3);
 }
 type StringBuilder = RustcCLRInteropManagedClass<"System.Runtime","System.Text.StringBuilder">;
 fn console_writeline(){
-    rustc_clr_interop_managed_call0_::<"System.Console","System.Console",false,"WriteLine",()>();
-    rustc_clr_interop_managed_call1_::<"System.Console","System.Console",false,"WriteLine",true,(),i32>(64);
+    rustc_clr_interop_managed_call0_::<"System.Console","System.Console",false,(),"WriteLine",()>();
+    rustc_clr_interop_managed_call1_::<"System.Console","System.Console",false,(),"WriteLine",true,(),i32>(64);
 }
 fn new_helloworld(){
-    let sb = rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.Text.StringBuilder",false>();
+    let sb = rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.Text.StringBuilder",false,()>();
     black_box(sb);
     let m_char = unsafe{core::mem::transmute::<u16,RustcCLRInteropManagedChar>(black_box(64))}; 
-    rustc_clr_interop_managed_call2_::<"System.Runtime","System.Text.StringBuilder",false,"Append",false,StringBuilder,StringBuilder,RustcCLRInteropManagedChar>(sb,m_char);
+    rustc_clr_interop_managed_call2_::<"System.Runtime","System.Text.StringBuilder",false,(),"Append",false,StringBuilder,StringBuilder,RustcCLRInteropManagedChar>(sb,m_char);
 }
@@ -0,0 +1,79 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedArray<T,const DIMENSIONS:usize>{
+    pd:core::marker::PhantomData<T>,
+    prevent_construction:usize,
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_array_new_<T,const DIMENSIONS:usize>(lengths:*const usize)->RustcCLRInteropManagedArray<T,DIMENSIONS>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_array_len_<T,const DIMENSIONS:usize>(array:RustcCLRInteropManagedArray<T,DIMENSIONS>)->usize{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_array_get_<T,const DIMENSIONS:usize>(array:RustcCLRInteropManagedArray<T,DIMENSIONS>,indices:*const usize)->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_array_set_<T,const DIMENSIONS:usize>(array:RustcCLRInteropManagedArray<T,DIMENSIONS>,indices:*const usize,value:T){
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_array_address_<T,const DIMENSIONS:usize>(array:RustcCLRInteropManagedArray<T,DIMENSIONS>,indices:*const usize)->*mut T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_gc_handle_pin_<T>(obj:T)->isize{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_gc_handle_free_(handle:isize){
+    core::intrinsics::abort();
+}
+fn main(){
+    vector();
+    matrix();
+    pinned();
+}
+fn vector(){
+    let lengths = [5_usize];
+    let array = rustc_clr_interop_managed_array_new_::<i32,1>(lengths.as_ptr());
+    test_eq!(rustc_clr_interop_managed_array_len_::<i32,1>(array),5);
+    for i in 0..5{
+        rustc_clr_interop_managed_array_set_::<i32,1>(array,[i].as_ptr(),(i * i) as i32);
+    }
+    test_eq!(rustc_clr_interop_managed_array_get_::<i32,1>(array,[0].as_ptr()),0);
+    test_eq!(rustc_clr_interop_managed_array_get_::<i32,1>(array,[3].as_ptr()),9);
+}
+fn matrix(){
+    let lengths = [2_usize,3];
+    let array = rustc_clr_interop_managed_array_new_::<f64,2>(lengths.as_ptr());
+    test_eq!(rustc_clr_interop_managed_array_len_::<f64,2>(array),6);
+    rustc_clr_interop_managed_array_set_::<f64,2>(array,[1,2].as_ptr(),2.5);
+    test_eq!(rustc_clr_interop_managed_array_get_::<f64,2>(array,[1,2].as_ptr()),2.5);
+    test_eq!(rustc_clr_interop_managed_array_get_::<f64,2>(array,[0,2].as_ptr()),0.0);
+}
+fn pinned(){
+    let lengths = [4_usize];
+    let array = rustc_clr_interop_managed_array_new_::<u8,1>(lengths.as_ptr());
+    let handle = rustc_clr_interop_gc_handle_pin_::<RustcCLRInteropManagedArray<u8,1>>(array);
+    let first = rustc_clr_interop_managed_array_address_::<u8,1>(array,[0].as_ptr());
+    let elements = unsafe{core::slice::from_raw_parts_mut(first,4)};
+    elements[2] = 42;
+    test_eq!(rustc_clr_interop_managed_array_get_::<u8,1>(array,[2].as_ptr()),42);
+    rustc_clr_interop_managed_array_set_::<u8,1>(array,[3].as_ptr(),7);
+    test_eq!(elements[3],7);
+    rustc_clr_interop_gc_handle_free_(handle);
+}
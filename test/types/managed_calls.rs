@@ -0,0 +1,76 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[allow(dead_code)]
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
+    prevent_construction:usize,
+}
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedChar{
+    size:u16,
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ctor0_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics>()->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_call4_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const METHOD:&'static str,const IS_STATIC:bool,Ret,Arg1,Arg2,Arg3,Arg4>(arg1:Arg1,arg2:Arg2,arg3:Arg3,arg4:Arg4)->Ret{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_call5_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const METHOD:&'static str,const IS_STATIC:bool,Ret,Arg1,Arg2,Arg3,Arg4,Arg5>(arg1:Arg1,arg2:Arg2,arg3:Arg3,arg4:Arg4,arg5:Arg5)->Ret{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ctor4_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,Arg1,Arg2,Arg3,Arg4>(arg1:Arg1,arg2:Arg2,arg3:Arg3,arg4:Arg4)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
+    core::intrinsics::abort();
+}
+type Version = RustcCLRInteropManagedClass<"System.Runtime","System.Version">;
+type StringBuilder = RustcCLRInteropManagedClass<"System.Runtime","System.Text.StringBuilder">;
+fn managed_char(utf16:u16)->RustcCLRInteropManagedChar{
+    unsafe{core::mem::transmute::<u16,RustcCLRInteropManagedChar>(black_box(utf16))}
+}
+fn char_at(builder:StringBuilder,index:i32)->u16{
+    let chr = rustc_clr_interop_managed_call2_::<"System.Runtime","System.Text.StringBuilder",false,(),"get_Chars",false,RustcCLRInteropManagedChar,StringBuilder,i32>(builder,index);
+    unsafe{core::mem::transmute::<RustcCLRInteropManagedChar,u16>(chr)}
+}
+fn main(){
+    static_call();
+    ctor_and_instance_calls();
+}
+fn static_call(){
+    let src:[u8;8] = [1,2,3,4,5,6,7,8];
+    let mut dst:[u8;8] = [0;8];
+    let src_ptr:*const () = core::ptr::addr_of!(src).cast();
+    let dst_ptr:*mut () = core::ptr::addr_of_mut!(dst).cast();
+    rustc_clr_interop_managed_call4_::<"System.Runtime","System.Buffer",false,(),"MemoryCopy",true,(),*const (),*mut (),i64,i64>(src_ptr,dst_ptr,8,6);
+    test_eq!(dst[0],1);
+    test_eq!(dst[5],6);
+    test_eq!(dst[6],0);
+}
+fn ctor_and_instance_calls(){
+    let version = rustc_clr_interop_managed_ctor4_::<"System.Runtime","System.Version",false,(),i32,i32,i32,i32>(1,2,3,4);
+    let major = rustc_clr_interop_managed_call1_::<"System.Runtime","System.Version",false,(),"get_Major",false,i32,Version>(version);
+    test_eq!(major,1);
+    let revision = rustc_clr_interop_managed_call1_::<"System.Runtime","System.Version",false,(),"get_Revision",false,i32,Version>(version);
+    test_eq!(revision,4);
+    let other = rustc_clr_interop_managed_ctor4_::<"System.Runtime","System.Version",false,(),i32,i32,i32,i32>(1,2,4,0);
+    let order = rustc_clr_interop_managed_call2_::<"System.Runtime","System.Version",false,(),"CompareTo",false,i32,Version,Version>(version,other);
+    test!(order < 0);
+    let builder = rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.Text.StringBuilder",false,()>();
+    let chars = [managed_char(0x61);4];
+    rustc_clr_interop_managed_call3_::<"System.Runtime","System.Text.StringBuilder",false,(),"Append",false,StringBuilder,StringBuilder,*const RustcCLRInteropManagedChar,i32>(builder,chars.as_ptr(),4);
+    // An instance call with 4 arguments besides `this`: replaces 'a' with 'b' at indices 1 and 2.
+    rustc_clr_interop_managed_call5_::<"System.Runtime","System.Text.StringBuilder",false,(),"Replace",false,StringBuilder,StringBuilder,RustcCLRInteropManagedChar,RustcCLRInteropManagedChar,i32,i32>(builder,managed_char(0x61),managed_char(0x62),1,2);
+    test_eq!(char_at(builder,0),0x61);
+    test_eq!(char_at(builder,1),0x62);
+    test_eq!(char_at(builder,2),0x62);
+    test_eq!(char_at(builder,3),0x61);
+}
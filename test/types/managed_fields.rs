@@ -0,0 +1,106 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[allow(dead_code)]
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
+    prevent_construction:usize,
+}
+#[derive(Clone,Copy)]
+#[repr(transparent)]
+struct RustcCLRInteropManagedGenericArg<T,const INDEX:usize>{
+    value:T,
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ctor0_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics>()->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ctor1_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,Arg1>(arg1:Arg1)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_generic_arg_wrap_<T,const INDEX:usize>(value:T)->RustcCLRInteropManagedGenericArg<T,INDEX>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_generic_arg_unwrap_<T,const INDEX:usize>(arg:RustcCLRInteropManagedGenericArg<T,INDEX>)->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ld_field_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const FIELD:&'static str,T>(obj:RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>)->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_st_field_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const FIELD:&'static str,T>(obj:RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>,value:T){
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ld_static_field_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const FIELD:&'static str,T>()->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_get_property_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const PROPERTY:&'static str,T>(obj:RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>)->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_set_property_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const PROPERTY:&'static str,T>(obj:RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>,value:T){
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_get_static_property_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const PROPERTY:&'static str,T>()->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_set_static_property_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,const PROPERTY:&'static str,T>(value:T){
+    core::intrinsics::abort();
+}
+type Value = RustcCLRInteropManagedGenericArg<i32,0>;
+fn main(){
+    instance_fields();
+    static_fields();
+    properties();
+    static_properties();
+}
+fn instance_fields(){
+    // `StrongBox<T>` is one of the few framework classes with a public instance field.
+    let strong_box = rustc_clr_interop_managed_ctor1_::<"System.Runtime","System.Runtime.CompilerServices.StrongBox`1",false,(i32,),Value>(rustc_clr_interop_managed_generic_arg_wrap_::<i32,0>(7));
+    let value = rustc_clr_interop_managed_ld_field_::<"System.Runtime","System.Runtime.CompilerServices.StrongBox`1",false,(i32,),"Value",Value>(strong_box);
+    test_eq!(rustc_clr_interop_managed_generic_arg_unwrap_::<i32,0>(value),7);
+    rustc_clr_interop_managed_st_field_::<"System.Runtime","System.Runtime.CompilerServices.StrongBox`1",false,(i32,),"Value",Value>(strong_box,rustc_clr_interop_managed_generic_arg_wrap_::<i32,0>(-3));
+    let value = rustc_clr_interop_managed_ld_field_::<"System.Runtime","System.Runtime.CompilerServices.StrongBox`1",false,(i32,),"Value",Value>(strong_box);
+    test_eq!(rustc_clr_interop_managed_generic_arg_unwrap_::<i32,0>(value),-3);
+}
+fn static_fields(){
+    let is_little_endian = rustc_clr_interop_managed_ld_static_field_::<"System.Runtime","System.BitConverter",false,(),"IsLittleEndian",bool>();
+    test_eq!(is_little_endian,cfg!(target_endian = "little"));
+}
+fn properties(){
+    let builder = rustc_clr_interop_managed_ctor0_::<"System.Runtime","System.Text.StringBuilder",false,()>();
+    rustc_clr_interop_managed_set_property_::<"System.Runtime","System.Text.StringBuilder",false,(),"Capacity",i32>(builder,64);
+    let capacity = rustc_clr_interop_managed_get_property_::<"System.Runtime","System.Text.StringBuilder",false,(),"Capacity",i32>(builder);
+    test_eq!(capacity,64);
+    let len = rustc_clr_interop_managed_get_property_::<"System.Runtime","System.Text.StringBuilder",false,(),"Length",i32>(builder);
+    test_eq!(len,0);
+}
+fn static_properties(){
+    let processor_count = rustc_clr_interop_managed_get_static_property_::<"System.Runtime","System.Environment",false,(),"ProcessorCount",i32>();
+    test!(processor_count > 0);
+    rustc_clr_interop_managed_set_static_property_::<"System.Runtime","System.Environment",false,(),"ExitCode",i32>(3);
+    let exit_code = rustc_clr_interop_managed_get_static_property_::<"System.Runtime","System.Environment",false,(),"ExitCode",i32>();
+    test_eq!(exit_code,3);
+    rustc_clr_interop_managed_set_static_property_::<"System.Runtime","System.Environment",false,(),"ExitCode",i32>(0);
+}
@@ -0,0 +1,67 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[allow(dead_code)]
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
+    prevent_construction:usize,
+}
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedChar{
+    size:u16,
+}
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedSpan<T,const READ_ONLY:bool>{
+    ptr:*const T,
+    len:usize,
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_span_new_<T,const READ_ONLY:bool>(ptr:*const T,len:usize)->RustcCLRInteropManagedSpan<T,READ_ONLY>{
+    core::intrinsics::abort();
+}
+type Encoding = RustcCLRInteropManagedClass<"System.Runtime","System.Text.Encoding">;
+type MString = RustcCLRInteropManagedClass<"System.Runtime","System.String">;
+fn utf8()->Encoding{
+    rustc_clr_interop_managed_call0_::<"System.Runtime","System.Text.Encoding",false,(),"get_UTF8",Encoding>()
+}
+fn from_utf8(bytes:&[u8])->MString{
+    rustc_clr_interop_managed_call3_::<"System.Runtime","System.Text.Encoding",false,(),"GetString",false,MString,Encoding,*const u8,i32>(utf8(),bytes.as_ptr(),bytes.len() as i32)
+}
+fn len(string:MString)->i32{
+    rustc_clr_interop_managed_call1_::<"System.Runtime","System.String",false,(),"get_Length",false,i32,MString>(string)
+}
+fn char_at(string:MString,index:i32)->u16{
+    let chr = rustc_clr_interop_managed_call2_::<"System.Runtime","System.String",false,(),"get_Chars",false,RustcCLRInteropManagedChar,MString,i32>(string,index);
+    unsafe{core::mem::transmute::<RustcCLRInteropManagedChar,u16>(chr)}
+}
+fn utf8_len(string:MString)->i32{
+    rustc_clr_interop_managed_call2_::<"System.Runtime","System.Text.Encoding",false,(),"GetByteCount",false,i32,Encoding,MString>(utf8(),string)
+}
+/// Encodes `string` into the uninitialized memory at `buf`, like `MString::to_utf8_vec` does.
+fn to_utf8(string:MString,buf:*mut u8,len:usize)->i32{
+    let chars = rustc_clr_interop_managed_call1_::<"System.Runtime","System.String",false,(),"op_Implicit",true,RustcCLRInteropManagedSpan<RustcCLRInteropManagedChar,true>,MString>(string);
+    let span = rustc_clr_interop_managed_span_new_::<u8,false>(buf,len);
+    rustc_clr_interop_managed_call3_::<"System.Runtime","System.Text.Encoding",false,(),"GetBytes",false,i32,Encoding,RustcCLRInteropManagedSpan<RustcCLRInteropManagedChar,true>,RustcCLRInteropManagedSpan<u8,false>>(utf8(),chars,span)
+}
+fn main(){
+    let ascii = from_utf8(b"Hello");
+    test_eq!(len(ascii),5);
+    test_eq!(char_at(ascii,0),'H' as u16);
+    test_eq!(char_at(ascii,4),'o' as u16);
+    test_eq!(utf8_len(ascii),5);
+    // "zółw": 'ó' and 'ł' are 2 bytes long in UTF-8, but 1 UTF-16 code unit long.
+    let polish = from_utf8(&[0x7A,0xC3,0xB3,0xC5,0x82,0x77]);
+    test_eq!(len(polish),4);
+    test_eq!(char_at(polish,1),0xF3);
+    test_eq!(char_at(polish,2),0x142);
+    test_eq!(utf8_len(polish),6);
+    let buf = unsafe{malloc(6)} as *mut u8;
+    test_eq!(to_utf8(polish,buf,6),6);
+    let encoded = unsafe{core::slice::from_raw_parts(buf,6)};
+    test_eq!(encoded[0],0x7A);
+    test_eq!(encoded[2],0xB3);
+    test_eq!(encoded[5],0x77);
+}
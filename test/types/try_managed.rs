@@ -0,0 +1,65 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[allow(dead_code)]
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
+    prevent_construction:usize,
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ctor2_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics,Arg1,Arg2>(arg1:Arg1,arg2:Arg2)->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
+    core::intrinsics::abort();
+}
+#[inline(never)]
+fn rustc_clr_interop_try_catch_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,T,F:FnOnce()->T>(f:F)->Result<T,isize>{
+    Ok(f())
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_gc_handle_target_<T>(handle:isize)->T{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_gc_handle_free_(handle:isize){
+    core::intrinsics::abort();
+}
+type Version = RustcCLRInteropManagedClass<"System.Runtime","System.Version">;
+type ArgumentException = RustcCLRInteropManagedClass<"System.Runtime","System.ArgumentException">;
+fn new_version(major:i32,minor:i32)->Version{
+    rustc_clr_interop_managed_ctor2_::<"System.Runtime","System.Version",false,(),i32,i32>(major,minor)
+}
+fn main(){
+    no_exception();
+    caught_exception();
+}
+fn no_exception(){
+    let result = rustc_clr_interop_try_catch_::<"System.Runtime","System.Exception",i32,_>(||{
+        let version = new_version(black_box(3),1);
+        rustc_clr_interop_managed_call1_::<"System.Runtime","System.Version",false,(),"get_Major",false,i32,Version>(version)
+    });
+    match result{
+        Ok(major)=>test_eq!(major,3),
+        Err(_)=>core::intrinsics::abort(),
+    }
+}
+fn caught_exception(){
+    let mut reached_end = false;
+    // `Version` throws `ArgumentOutOfRangeException` for negative components, which is caught as its base class.
+    let result = rustc_clr_interop_try_catch_::<"System.Runtime","System.ArgumentException",(),_>(||{
+        new_version(black_box(-1),0);
+        reached_end = true;
+    });
+    test!(!reached_end);
+    let Err(handle) = result else{
+        core::intrinsics::abort();
+    };
+    let exception = rustc_clr_interop_gc_handle_target_::<ArgumentException>(handle);
+    let hresult = rustc_clr_interop_managed_call1_::<"System.Runtime","System.ArgumentException",false,(),"get_HResult",false,i32,ArgumentException>(exception);
+    // COR_E_ARGUMENTOUTOFRANGE
+    test_eq!(hresult as u32,0x8013_1502);
+    rustc_clr_interop_gc_handle_free_(handle);
+}
@@ -0,0 +1,89 @@
+#![feature(lang_items,adt_const_params,associated_type_defaults,core_intrinsics,start)]
+#![allow(internal_features,incomplete_features,unused_variables,dead_code,improper_ctypes_definitions,improper_ctypes)]
+#![no_std]
+include!("../common.rs");
+#[allow(dead_code)]
+#[derive(Clone,Copy)]
+struct RustcCLRInteropManagedClass<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,Generics = ()>{
+    pd:core::marker::PhantomData<Generics>,
+    prevent_construction:usize,
+}
+#[derive(Clone,Copy)]
+#[repr(transparent)]
+struct RustcCLRInteropManagedGenericArg<T,const INDEX:usize>{
+    value:T,
+}
+trait RustcCLRInteropVirtuals<const ASSEMBLY:&'static str,const TYPE_PATH:&'static str,const IS_INTERFACE:bool,Generics = ()>{}
+impl<T:?Sized,const ASSEMBLY:&'static str,const TYPE_PATH:&'static str,const IS_INTERFACE:bool,Generics> RustcCLRInteropVirtuals<ASSEMBLY,TYPE_PATH,IS_INTERFACE,Generics> for T{}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_ctor0_<const ASSEMBLY:&'static str,const CLASS_PATH:&'static str,const IS_VALUETYPE:bool,Generics>()->RustcCLRInteropManagedClass<ASSEMBLY,CLASS_PATH,Generics>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_generic_arg_wrap_<T,const INDEX:usize>(value:T)->RustcCLRInteropManagedGenericArg<T,INDEX>{
+    core::intrinsics::abort();
+}
+#[allow(dead_code)]
+#[inline(never)]
+fn rustc_clr_interop_managed_generic_arg_unwrap_<T,const INDEX:usize>(arg:RustcCLRInteropManagedGenericArg<T,INDEX>)->T{
+    core::intrinsics::abort();
+}
+trait IComparable<T>:RustcCLRInteropVirtuals<"System.Runtime","System.IComparable`1",true,(T,)>{
+    fn compare_to(&self,other:T)->i32;
+}
+trait ObjectVirtuals:RustcCLRInteropVirtuals<"System.Runtime","System.Object",false>{
+    fn get_hash_code(&self)->i32;
+}
+#[derive(Clone,Copy)]
+struct Priority{
+    value:i32,
+}
+// Sorts priorities from the highest to the lowest.
+impl IComparable<Priority> for Priority{
+    #[inline(never)]
+    fn compare_to(&self,other:Priority)->i32{
+        other.value - self.value
+    }
+}
+static mut HASH_CALLS:u32 = 0;
+impl ObjectVirtuals for Priority{
+    #[inline(never)]
+    fn get_hash_code(&self)->i32{
+        unsafe{HASH_CALLS += 1};
+        self.value
+    }
+}
+type List = RustcCLRInteropManagedClass<"System.Collections","System.Collections.Generic.List`1",(Priority,)>;
+type Item = RustcCLRInteropManagedGenericArg<Priority,0>;
+type Dictionary = RustcCLRInteropManagedClass<"System.Collections","System.Collections.Generic.Dictionary`2",(Priority,i32)>;
+type Key = RustcCLRInteropManagedGenericArg<Priority,0>;
+type Value = RustcCLRInteropManagedGenericArg<i32,1>;
+fn main(){
+    // Only methods used by Rust code are codegened, so both methods are called once directly.
+    let priority = black_box(Priority{value:2});
+    test_eq!(priority.compare_to(Priority{value:5}),3);
+    test_eq!(priority.get_hash_code(),2);
+    unsafe{HASH_CALLS = 0};
+    interface();
+    object_override();
+}
+fn interface(){
+    let list = rustc_clr_interop_managed_ctor0_::<"System.Collections","System.Collections.Generic.List`1",false,(Priority,)>();
+    for value in [3,7,1,5]{
+        rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.List`1",false,(Priority,),"Add",false,(),List,Item>(list,rustc_clr_interop_managed_generic_arg_wrap_::<Priority,0>(Priority{value}));
+    }
+    // `List<T>.Sort` compares elements using `IComparable<T>.CompareTo`, which is implemented in Rust.
+    rustc_clr_interop_managed_call1_::<"System.Collections","System.Collections.Generic.List`1",false,(Priority,),"Sort",false,(),List>(list);
+    let first = rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.List`1",false,(Priority,),"get_Item",false,Item,List,i32>(list,0);
+    test_eq!(rustc_clr_interop_managed_generic_arg_unwrap_::<Priority,0>(first).value,7);
+    let last = rustc_clr_interop_managed_call2_::<"System.Collections","System.Collections.Generic.List`1",false,(Priority,),"get_Item",false,Item,List,i32>(list,3);
+    test_eq!(rustc_clr_interop_managed_generic_arg_unwrap_::<Priority,0>(last).value,1);
+}
+fn object_override(){
+    let dict = rustc_clr_interop_managed_ctor0_::<"System.Collections","System.Collections.Generic.Dictionary`2",false,(Priority,i32)>();
+    // `Dictionary<K,V>` hashes keys using the overriden `Object.GetHashCode`.
+    rustc_clr_interop_managed_call3_::<"System.Collections","System.Collections.Generic.Dictionary`2",false,(Priority,i32),"set_Item",false,(),Dictionary,Key,Value>(dict,rustc_clr_interop_managed_generic_arg_wrap_::<Priority,0>(Priority{value:4}),rustc_clr_interop_managed_generic_arg_wrap_::<i32,1>(16));
+    test!(unsafe{HASH_CALLS} > 0);
+}